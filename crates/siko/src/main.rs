@@ -1,11 +1,19 @@
+use crate::repl::Repl;
 use colored::*;
 use siko_compiler::compiler::Compiler;
 use siko_compiler::compiler::CompilerInput;
 use siko_compiler::config::Config;
+use siko_compiler::diagnostic::ErrorFormat;
+use siko_compiler::error::Error;
 use std::env;
 use std::path::Path;
 use walkdir::WalkDir;
 
+mod repl;
+
+const COMPILE_ERROR_EXIT_CODE: i32 = 1;
+const RUNTIME_ERROR_EXIT_CODE: i32 = 2;
+
 fn process_dir(arg: String, inputs: &mut Vec<CompilerInput>) -> bool {
     let path = Path::new(&arg);
     if !path.exists() {
@@ -36,20 +44,57 @@ fn process_dir(arg: String, inputs: &mut Vec<CompilerInput>) -> bool {
     true
 }
 
-fn process_args(args: Vec<String>) -> (Config, Vec<CompilerInput>, bool) {
+fn process_args(args: Vec<String>) -> (Config, Vec<CompilerInput>, String, bool, bool) {
     let mut inputs = Vec::new();
     let mut config = Config::new();
     let mut success = true;
     let mut std_path = format!("std");
     let mut file_given = false;
+    let mut repl = false;
     for (index, arg) in args.iter().enumerate() {
         match arg.as_ref() {
+            "--" => {
+                config.program_args = args[index + 1..].to_vec();
+                break;
+            }
+            "check" if index == 0 => {
+                config.check_only = true;
+            }
+            "repl" if index == 0 => {
+                repl = true;
+            }
             "-m" => {
                 config.measure_durations = true;
             }
             "-i" => {
                 config.visualize = true;
             }
+            "--wrapping-arithmetic" => {
+                config.wrapping_arithmetic = true;
+            }
+            "--list-unused-externs" => {
+                config.list_unused_externs = true;
+            }
+            "--error-format=text" => {
+                config.error_format = ErrorFormat::Text;
+            }
+            "--error-format=json" => {
+                config.error_format = ErrorFormat::Json;
+            }
+            _ if arg.starts_with("--max-call-depth=") => {
+                let value = &arg["--max-call-depth=".len()..];
+                match value.parse() {
+                    Ok(depth) => config.max_call_depth = depth,
+                    Err(_) => {
+                        eprintln!(
+                            "{} invalid max call depth {}",
+                            "ERROR:".red(),
+                            value.yellow()
+                        );
+                        success = false;
+                    }
+                }
+            }
             "-s" => {
                 if index + 1 >= args.len() {
                     eprintln!("{} missing path after -s", "ERROR:".red(),);
@@ -59,10 +104,16 @@ fn process_args(args: Vec<String>) -> (Config, Vec<CompilerInput>, bool) {
                 }
             }
             "-h" => {
-                println!("arguments: <filename>+|<options>");
+                println!("arguments: [check|repl] <filename>+|<options> [-- <program arguments>]");
+                println!("check stop after type checking, main is not required");
+                println!("repl start an interactive session with the given files loaded");
                 println!("-m measure durations");
                 println!("-i visualize");
                 println!("-s <path> path to std");
+                println!("--error-format=text|json format of the reported errors");
+                println!("--max-call-depth=<n> maximum depth of nested calls at runtime");
+                println!("--wrapping-arithmetic integer overflow wraps around instead of failing");
+                println!("--list-unused-externs list the extern implementations no declaration uses");
                 success = false;
             }
             _ => {
@@ -73,34 +124,50 @@ fn process_args(args: Vec<String>) -> (Config, Vec<CompilerInput>, bool) {
             }
         }
     }
-    if !file_given  {
+    if !file_given && !repl {
         if success {
-            eprintln!("no file given to compile");    
+            eprintln!("no file given to compile");
         }
         success = false;
-        
-    }
-    if success {
-        if !process_dir(std_path, &mut inputs) {
-            success = false;
-        }
     }
     //println!("Compiling {} file(s)", inputs.len());
-    (config, inputs, success)
+    (config, inputs, std_path, repl, success)
 }
 
 fn main() {
     let args: Vec<_> = env::args().skip(1).collect();
 
-    let (config, inputs, success) = process_args(args);
+    let (config, mut inputs, std_path, repl, success) = process_args(args);
 
     if !success {
         return;
     }
 
+    if repl {
+        let mut repl = Repl::new(inputs, std_path);
+        repl.run();
+        return;
+    }
+
+    if !process_dir(std_path, &mut inputs) {
+        return;
+    }
+
     let mut compiler = Compiler::new(config);
 
-    if let Err(e) = compiler.compile(inputs) {
-        compiler.report_error(e);
+    match compiler.compile(inputs) {
+        Ok(exit_code) => {
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
+        }
+        Err(e) => {
+            let exit_code = match &e {
+                Error::RuntimeError(..) => RUNTIME_ERROR_EXIT_CODE,
+                _ => COMPILE_ERROR_EXIT_CODE,
+            };
+            compiler.report_error(e);
+            std::process::exit(exit_code);
+        }
     }
 }
@@ -0,0 +1,446 @@
+use crate::process_dir;
+use colored::*;
+use siko_compiler::compiler::Compiler;
+use siko_compiler::compiler::CompilerInput;
+use siko_compiler::config::Config;
+use siko_interpreter::interpreter::Interpreter;
+use siko_interpreter::value::Value;
+use siko_ir::function::FunctionInfo;
+use siko_ir::program::Program;
+use siko_ir::types::Type;
+use siko_syntax::program::Program as SyntaxProgram;
+use std::collections::BTreeSet;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+const REPL_MODULE: &str = "Repl";
+const REPL_FILE: &str = "<repl>";
+const REPL_VALUE: &str = "replValue";
+const REPL_SHOW: &str = "replShow";
+
+fn get_function_type(program: &Program, module: &str, name: &str) -> Type {
+    for (id, function) in &program.functions.items {
+        if let FunctionInfo::NamedFunction(info) = &function.info {
+            if info.module == module && info.name == name {
+                let ty = program.function_types.get(id).expect("untyped func");
+                return ty.clone();
+            }
+        }
+    }
+    panic!("Cannot find function {} in module {}", name, module);
+}
+
+// A top level definition of the session and the names it introduces
+#[derive(Clone)]
+struct Definition {
+    text: String,
+    functions: BTreeSet<String>,
+    function_types: BTreeSet<String>,
+    types: BTreeSet<String>,
+}
+
+impl Definition {
+    fn new(text: &str, program: &SyntaxProgram) -> Definition {
+        let mut functions = BTreeSet::new();
+        let mut function_types = BTreeSet::new();
+        let mut types = BTreeSet::new();
+        for module in program.modules.items.values() {
+            functions.extend(module.functions.keys().cloned());
+            function_types.extend(module.function_types.keys().cloned());
+            for id in &module.records {
+                types.insert(program.records.get(id).name.clone());
+            }
+            for id in &module.adts {
+                types.insert(program.adts.get(id).name.clone());
+            }
+            for id in &module.classes {
+                types.insert(program.classes.get(id).name.clone());
+            }
+            for id in &module.protocols {
+                types.insert(program.protocols.get(id).name.clone());
+            }
+            for id in &module.actors {
+                types.insert(program.actors.get(id).name.clone());
+            }
+        }
+        Definition {
+            text: text.to_string(),
+            functions: functions,
+            function_types: function_types,
+            types: types,
+        }
+    }
+
+    // Whether this definition has to be dropped when the new one is added,
+    // redefined_functions are the functions of the new one which are already defined
+    fn is_replaced_by(&self, new: &Definition, redefined_functions: &BTreeSet<String>) -> bool {
+        !self.functions.is_disjoint(&new.functions)
+            || !self.function_types.is_disjoint(&new.function_types)
+            || !self.function_types.is_disjoint(redefined_functions)
+            || !self.types.is_disjoint(&new.types)
+    }
+}
+
+enum InputKind {
+    Import,
+    Definition(Definition),
+    Expression,
+}
+
+// Anything the parser accepts as a module item is a definition,
+// everything else is evaluated as an expression.
+fn get_input_kind(input: &str) -> InputKind {
+    let mut compiler = Compiler::new(Config::new());
+    let mut program = SyntaxProgram::new();
+    let source = CompilerInput::Memory {
+        name: REPL_FILE.to_string(),
+        content: format!("module {} where\n\n{}\n", REPL_MODULE, input),
+    };
+    if compiler.parse_inputs(&[source], &mut program).is_err() {
+        return InputKind::Expression;
+    }
+    // implicit imports have no location
+    if program
+        .imports
+        .items
+        .values()
+        .any(|import| import.location_id.is_some())
+    {
+        InputKind::Import
+    } else {
+        InputKind::Definition(Definition::new(input, &program))
+    }
+}
+
+fn indent(text: &str) -> String {
+    let lines: Vec<_> = text.lines().map(|line| format!("    {}", line)).collect();
+    lines.join("\n")
+}
+
+pub struct Repl {
+    std_path: String,
+    files: Vec<CompilerInput>,
+    // std and the loaded files are parsed once per reload,
+    // only the REPL module is parsed for each input
+    compiler: Compiler,
+    program: SyntaxProgram,
+    modules: Vec<String>,
+    imports: Vec<String>,
+    definitions: Vec<Definition>,
+}
+
+impl Repl {
+    pub fn new(files: Vec<CompilerInput>, std_path: String) -> Repl {
+        Repl {
+            std_path: std_path,
+            files: files,
+            compiler: Compiler::new(Config::new()),
+            program: SyntaxProgram::new(),
+            modules: Vec::new(),
+            imports: Vec::new(),
+            definitions: Vec::new(),
+        }
+    }
+
+    fn reload(&mut self) -> bool {
+        let mut std_inputs = Vec::new();
+        if !process_dir(self.std_path.clone(), &mut std_inputs) {
+            return false;
+        }
+        let mut compiler = Compiler::new(Config::new());
+        let mut program = SyntaxProgram::new();
+        if let Err(e) = compiler.parse_inputs(&self.files, &mut program) {
+            compiler.report_error(e);
+            return false;
+        }
+        let modules = program
+            .modules
+            .items
+            .values()
+            .map(|module| module.name.clone())
+            .collect();
+        if let Err(e) = compiler.parse_inputs(&std_inputs, &mut program) {
+            compiler.report_error(e);
+            return false;
+        }
+        self.compiler = compiler;
+        self.program = program;
+        self.modules = modules;
+        self.check_session()
+    }
+
+    fn load(&mut self, path: &str) -> bool {
+        let mut files = Vec::new();
+        if !process_dir(path.to_string(), &mut files) {
+            return false;
+        }
+        let previous_files = self.files.clone();
+        self.files.extend(files);
+        if self.reload() {
+            return true;
+        }
+        self.files = previous_files;
+        self.reload();
+        false
+    }
+
+    fn get_source(&self, item: &str) -> String {
+        let mut source = format!("module {} where\n\n", REPL_MODULE);
+        for module in &self.modules {
+            source += &format!("import {}\n", module);
+        }
+        for import in &self.imports {
+            source += &format!("{}\n", import);
+        }
+        for definition in &self.definitions {
+            source += &format!("\n{}\n", definition.text);
+        }
+        source += &format!("\n{}\n", item);
+        source
+    }
+
+    fn compile(&self, item: &str, report_errors: bool) -> Option<(Compiler, Program)> {
+        let mut compiler = self.compiler.clone();
+        let mut program = self.program.clone();
+        let input = CompilerInput::Memory {
+            name: REPL_FILE.to_string(),
+            content: self.get_source(item),
+        };
+        let registry = Interpreter::create_extern_registry();
+        let result = compiler
+            .parse_inputs(&[input], &mut program)
+            .and_then(|_| compiler.check_program(&program, false, &registry));
+        match result {
+            Ok(program) => Some((compiler, program)),
+            Err(e) => {
+                if report_errors {
+                    compiler.report_error(e);
+                }
+                None
+            }
+        }
+    }
+
+    fn check(&self, item: &str) -> Option<(Compiler, Program)> {
+        self.compile(item, true)
+    }
+
+    fn check_session(&self) -> bool {
+        // a module must not be empty, so a placeholder item is always added
+        self.check(&format!("{} = ()", REPL_VALUE)).is_some()
+    }
+
+    fn run_function(compiler: Compiler, program: Program, name: &str) -> Option<Value> {
+        match compiler.run_function(program, REPL_MODULE, name) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                compiler.report_error(e);
+                None
+            }
+        }
+    }
+
+    fn get_value_item(expr: &str) -> String {
+        format!("{} =\n{}", REPL_VALUE, indent(expr))
+    }
+
+    fn show_type(&self, expr: &str) {
+        if let Some((_, program)) = self.check(&Repl::get_value_item(expr)) {
+            let ty = get_function_type(&program, REPL_MODULE, REPL_VALUE);
+            println!("{}", ty.get_resolved_type_string(&program));
+        }
+    }
+
+    fn evaluate(&self, expr: &str) {
+        let value_item = Repl::get_value_item(expr);
+        let show_item = format!("{}\n\n{} = show {}", value_item, REPL_SHOW, REPL_VALUE);
+        match self.compile(&show_item, false) {
+            Some((compiler, program)) => {
+                let ty = get_function_type(&program, REPL_MODULE, REPL_VALUE);
+                let ty = ty.get_resolved_type_string(&program);
+                if let Some(value) = Repl::run_function(compiler, program, REPL_SHOW) {
+                    println!("{} :: {}", value.core.as_string(), ty);
+                }
+            }
+            None => {
+                // The value itself may be fine, it just cannot be shown.
+                if let Some((compiler, program)) = self.check(&value_item) {
+                    let ty = get_function_type(&program, REPL_MODULE, REPL_VALUE);
+                    let ty_str = ty.get_resolved_type_string(&program);
+                    if !ty.is_concrete_type() {
+                        println!("<not showable> :: {}", ty_str);
+                    } else if Repl::run_function(compiler, program, REPL_VALUE).is_some() {
+                        if ty == Type::Tuple(vec![]) {
+                            println!("() :: {}", ty_str);
+                        } else {
+                            println!("<not showable> :: {}", ty_str);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_import(&mut self, text: &str) {
+        self.imports.push(text.to_string());
+        if !self.check_session() {
+            self.imports.pop();
+        }
+    }
+
+    // A definition replaces the earlier ones with the same names,
+    // a function also replaces the signature of its earlier definition.
+    fn add_definition(&mut self, definition: Definition) {
+        let redefined_functions: BTreeSet<_> = definition
+            .functions
+            .iter()
+            .filter(|name| self.definitions.iter().any(|d| d.functions.contains(*name)))
+            .cloned()
+            .collect();
+        let mut definitions: Vec<_> = self
+            .definitions
+            .iter()
+            .filter(|d| !d.is_replaced_by(&definition, &redefined_functions))
+            .cloned()
+            .collect();
+        definitions.push(definition);
+        let previous = std::mem::replace(&mut self.definitions, definitions);
+        if !self.check_session() {
+            self.definitions = previous;
+        }
+    }
+
+    fn print_help() {
+        println!("<expr>          evaluate an expression");
+        println!("<definition>    add a top level definition");
+        println!(":type <expr>    show the type of an expression");
+        println!(":load <path>    load a file or directory");
+        println!(":reload         reload all loaded files");
+        println!(":{{ ... :}}       multi-line input");
+        println!(":quit           exit");
+    }
+
+    fn process_command(&mut self, input: &str) -> bool {
+        let (command, arg) = match input.find(' ') {
+            Some(index) => (&input[..index], input[index..].trim()),
+            None => (input, ""),
+        };
+        match command {
+            ":t" | ":type" => self.show_type(arg),
+            ":l" | ":load" => {
+                if self.load(arg) {
+                    println!("loaded {}", arg);
+                }
+            }
+            ":r" | ":reload" => {
+                if self.reload() {
+                    println!("reloaded");
+                }
+            }
+            ":h" | ":help" => Repl::print_help(),
+            ":q" | ":quit" => return false,
+            _ => eprintln!("{} unknown command {}", "ERROR:".red(), command.yellow()),
+        }
+        true
+    }
+
+    fn process_input(&mut self, input: &str) -> bool {
+        if input.starts_with(':') {
+            return self.process_command(input);
+        }
+        match get_input_kind(input) {
+            InputKind::Import => self.add_import(input),
+            InputKind::Definition(definition) => self.add_definition(definition),
+            InputKind::Expression => self.evaluate(input),
+        }
+        true
+    }
+
+    pub fn run(&mut self) {
+        self.reload();
+        let stdin = io::stdin();
+        let mut block: Option<Vec<String>> = None;
+        loop {
+            if block.is_some() {
+                print!("| ");
+            } else {
+                print!("> ");
+            }
+            io::stdout().flush().expect("Flush failed");
+            let mut line = String::new();
+            match stdin.lock().read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            let line = line.trim_end();
+            if let Some(lines) = &mut block {
+                if line.trim() == ":}" {
+                    let input = lines.join("\n");
+                    block = None;
+                    if !self.process_input(&input) {
+                        break;
+                    }
+                } else {
+                    lines.push(line.to_string());
+                }
+                continue;
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line == ":{" {
+                block = Some(Vec::new());
+                continue;
+            }
+            if !self.process_input(line) {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_repl() -> Repl {
+        let std_path = format!("{}/../../std", env!("CARGO_MANIFEST_DIR"));
+        let mut repl = Repl::new(Vec::new(), std_path);
+        assert!(repl.reload());
+        repl
+    }
+
+    fn eval_int(repl: &Repl, expr: &str) -> i64 {
+        let (compiler, program) = repl.compile(&Repl::get_value_item(expr), true).unwrap();
+        let value = Repl::run_function(compiler, program, REPL_VALUE).unwrap();
+        value.core.as_int()
+    }
+
+    #[test]
+    fn redefine_function() {
+        let mut repl = create_repl();
+        repl.process_input("f x = x");
+        repl.process_input("f x = x + 1");
+        assert_eq!(repl.definitions.len(), 1);
+        assert_eq!(eval_int(&repl, "f 1"), 2);
+    }
+
+    #[test]
+    fn redefine_function_with_signature() {
+        let mut repl = create_repl();
+        repl.process_input("g :: Int -> Int\ng x = x");
+        repl.process_input("g x = x * 3");
+        assert_eq!(repl.definitions.len(), 1);
+        assert_eq!(eval_int(&repl, "g 2"), 6);
+    }
+
+    #[test]
+    fn failed_redefinition_keeps_previous() {
+        let mut repl = create_repl();
+        repl.process_input("h x = x + 1");
+        repl.process_input("h x = x + missing");
+        assert_eq!(eval_int(&repl, "h 1"), 2);
+    }
+}
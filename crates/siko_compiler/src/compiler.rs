@@ -1,10 +1,28 @@
 use crate::config::Config;
+use crate::diagnostic::Diagnostic;
+use crate::diagnostic::ErrorFormat;
 use crate::error::Error;
+use crate::error::ExternError;
+use colored::*;
+use siko_constants::INT_MODULE_NAME;
+use siko_constants::INT_TYPE_NAME;
+use siko_constants::MAIN_FUNCTION;
+use siko_constants::MAIN_MODULE;
+use siko_constants::RESULT_MODULE_NAME;
+use siko_constants::RESULT_TYPE_NAME;
+use siko_interpreter::error::RuntimeError;
+use siko_interpreter::extern_function::ExternRegistry;
 use siko_interpreter::interpreter::Interpreter;
+use siko_interpreter::value::Value;
+use siko_interpreter::value::ValueCore;
 use siko_ir::expr_visualizer::ExprVisualizer;
+use siko_ir::function::FunctionInfo;
+use siko_ir::program::Program as IrProgram;
+use siko_ir::types::Type;
 use siko_location_info::error_context::ErrorContext;
 use siko_location_info::file_manager::FileManager;
 use siko_location_info::filepath::FilePath;
+use siko_location_info::location_id::LocationId;
 use siko_location_info::location_info::LocationInfo;
 use siko_name_resolver::resolver::Resolver;
 use siko_parser::lexer::Lexer;
@@ -13,12 +31,66 @@ use siko_syntax::program::Program;
 use siko_type_checker::typechecker::Typechecker;
 use siko_util::ElapsedTimeMeasure;
 use siko_util::ElapsedTimeMeasureCollector;
+use std::collections::BTreeSet;
 
+const MAX_EXIT_CODE: i64 = 255;
+
+// main may return an Int which is used as the exit code or a Result which
+// sets the exit code to 1 if it is an Err, any other return type means success.
+fn get_exit_code(
+    interpreter: &Interpreter,
+    main_ty: &Type,
+    main_location: LocationId,
+    value: Value,
+) -> Result<i32, RuntimeError> {
+    let program = interpreter.get_program();
+    let int_id = program.get_named_type(INT_MODULE_NAME, INT_TYPE_NAME);
+    let result_adt = program.get_adt_by_name(RESULT_MODULE_NAME, RESULT_TYPE_NAME);
+    match main_ty {
+        Type::Named(_, id, _) if *id == int_id => {
+            let code = value.core.as_int();
+            if code < 0 || code > MAX_EXIT_CODE {
+                return Err(RuntimeError {
+                    message: format!("Exit code {} is out of range 0..{}", code, MAX_EXIT_CODE),
+                    location_id: Some(main_location),
+                    stack: Vec::new(),
+                });
+            }
+            Ok(code as i32)
+        }
+        Type::Named(_, id, _) if *id == result_adt.id => match value.core {
+            ValueCore::Variant(_, index, mut items) => {
+                if result_adt.variants[index].name == "Ok" {
+                    Ok(0)
+                } else {
+                    let msg = interpreter.call_show(items.remove(0))?;
+                    eprintln!("{} {}", "ERROR:".red(), msg);
+                    Ok(1)
+                }
+            }
+            _ => unreachable!(),
+        },
+        _ => Ok(0),
+    }
+}
+
+fn get_main(program: &IrProgram) -> (Type, LocationId) {
+    for (id, function) in &program.functions.items {
+        if let FunctionInfo::NamedFunction(info) = &function.info {
+            if info.module == MAIN_MODULE && info.name == MAIN_FUNCTION {
+                let ty = program.function_types.get(id).expect("untyped func");
+                return (ty.clone(), info.location_id);
+            }
+        }
+    }
+    panic!("main not found");
+}
+
+#[derive(Clone)]
 pub enum CompilerInput {
     File {
         name: String,
     },
-    #[allow(unused)]
     Memory {
         name: String,
         content: String,
@@ -52,6 +124,7 @@ fn parse(
     Ok(())
 }
 
+#[derive(Clone)]
 pub struct Compiler {
     file_manager: FileManager,
     location_info: LocationInfo,
@@ -67,19 +140,106 @@ impl Compiler {
         }
     }
 
-    pub fn compile(&mut self, inputs: Vec<CompilerInput>) -> Result<(), Error> {
+    // Returns the exit code of the program
+    pub fn compile(&mut self, inputs: Vec<CompilerInput>) -> Result<i32, Error> {
+        let ir_program = self.compile_program(inputs, !self.config.check_only)?;
+
+        if self.config.check_only {
+            return Ok(0);
+        }
+
+        let (main_ty, main_location) = get_main(&ir_program);
+        let interpreter = Interpreter::create(
+            ir_program,
+            self.config.max_call_depth,
+            self.config.wrapping_arithmetic,
+            self.config.program_args.clone(),
+            Interpreter::create_extern_registry(),
+        );
+        let value = interpreter.run_function(MAIN_MODULE, MAIN_FUNCTION)?;
+        let exit_code = get_exit_code(&interpreter, &main_ty, main_location, value)?;
+        Ok(exit_code)
+    }
+
+    // Every extern declaration must have a registered implementation
+    fn check_extern_functions(
+        &self,
+        program: &IrProgram,
+        registry: &ExternRegistry,
+    ) -> Result<(), Error> {
+        let mut errors = Vec::new();
+        let mut used = BTreeSet::new();
+        for (_, function) in &program.functions.items {
+            if let FunctionInfo::NamedFunction(info) = &function.info {
+                if info.body.is_some() {
+                    continue;
+                }
+                if registry.contains(&info.module, &info.name) {
+                    used.insert((info.module.clone(), info.name.clone()));
+                } else {
+                    let name = format!("{}.{}", info.module, info.name);
+                    let err = ExternError::UnimplementedExternFunction(name, info.location_id);
+                    errors.push(err);
+                }
+            }
+        }
+        if self.config.list_unused_externs {
+            for (module, name) in registry.get_names() {
+                if !used.contains(&(module.clone(), name.clone())) {
+                    println!("unused extern function {}.{}", module, name);
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ExternError(errors))
+        }
+    }
+
+    pub fn compile_program(
+        &mut self,
+        inputs: Vec<CompilerInput>,
+        main_required: bool,
+    ) -> Result<IrProgram, Error> {
+        let registry = Interpreter::create_extern_registry();
+        self.compile_program_with_externs(inputs, main_required, &registry)
+    }
+
+    pub fn compile_program_with_externs(
+        &mut self,
+        inputs: Vec<CompilerInput>,
+        main_required: bool,
+        registry: &ExternRegistry,
+    ) -> Result<IrProgram, Error> {
         let mut program = Program::new();
+        self.parse_inputs(&inputs, &mut program)?;
+        self.check_program(&program, main_required, registry)
+    }
+
+    // Parses the inputs into the program, which may already contain modules
+    // parsed earlier by this compiler.
+    pub fn parse_inputs(
+        &mut self,
+        inputs: &[CompilerInput],
+        program: &mut Program,
+    ) -> Result<(), Error> {
+        let mut file_paths = BTreeSet::new();
 
         {
             let _m = ElapsedTimeMeasure::new("FileReader");
             for input in inputs.iter() {
                 match input {
                     CompilerInput::File { name } => {
-                        self.file_manager.read(FilePath::new(name.to_string()))?;
+                        let file_path = FilePath::new(name.to_string());
+                        self.file_manager.read(file_path.clone())?;
+                        file_paths.insert(file_path);
                     }
                     CompilerInput::Memory { name, content } => {
+                        let file_path = FilePath::new(name.to_string());
                         self.file_manager
-                            .add_from_memory(FilePath::new(name.to_string()), content.clone());
+                            .add_from_memory(file_path.clone(), content.clone());
+                        file_paths.insert(file_path);
                     }
                 }
             }
@@ -87,30 +247,41 @@ impl Compiler {
 
         {
             let _m = ElapsedTimeMeasure::new("Parser");
-            for (file_path, content) in self.file_manager.files.iter() {
+            for file_path in file_paths {
                 parse(
-                    content,
+                    self.file_manager.content(&file_path),
                     file_path.clone(),
-                    &mut program,
+                    program,
                     &mut self.location_info,
                 )?;
             }
         }
 
+        Ok(())
+    }
+
+    pub fn check_program(
+        &self,
+        program: &Program,
+        main_required: bool,
+        registry: &ExternRegistry,
+    ) -> Result<IrProgram, Error> {
         let mut ir_program = {
             let _m = ElapsedTimeMeasure::new("NameResolver");
             let mut resolver = Resolver::new();
 
-            resolver.resolve(&program)?
+            resolver.resolve(program)?
         };
 
         {
             let _m = ElapsedTimeMeasure::new("Typechecker");
             let typechecker = Typechecker::new();
 
-            typechecker.check(&mut ir_program)?;
+            typechecker.check(&mut ir_program, main_required)?;
         }
 
+        self.check_extern_functions(&ir_program, registry)?;
+
         if self.config.visualize {
             for (id, _) in &ir_program.functions.items {
                 ExprVisualizer::generate(&id, &ir_program);
@@ -119,10 +290,24 @@ impl Compiler {
 
         ElapsedTimeMeasureCollector::print_instance_resolver_time();
 
-        Interpreter::run(ir_program, self.context());
+        Ok(ir_program)
+    }
 
-        //println!("Result {}", value);
-        Ok(())
+    pub fn run_function(
+        &self,
+        program: IrProgram,
+        module: &str,
+        name: &str,
+    ) -> Result<Value, Error> {
+        let value = Interpreter::run(
+            program,
+            module,
+            name,
+            self.config.max_call_depth,
+            self.config.wrapping_arithmetic,
+            self.config.program_args.clone(),
+        )?;
+        Ok(value)
     }
 
     fn context(&self) -> ErrorContext {
@@ -132,7 +317,19 @@ impl Compiler {
         }
     }
 
+    // The diagnostics of the error without colors
+    pub fn get_diagnostics(&self, error: Error) -> Vec<Diagnostic> {
+        error
+            .get_diagnostics(&self.context())
+            .into_iter()
+            .map(|diagnostic| diagnostic.without_colors())
+            .collect()
+    }
+
     pub fn report_error(&self, error: Error) {
-        error.report_error(&self.context());
+        match self.config.error_format {
+            ErrorFormat::Text => error.report_error(&self.context()),
+            ErrorFormat::Json => error.report_error_json(&self.context()),
+        }
     }
 }
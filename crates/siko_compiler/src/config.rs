@@ -1,6 +1,15 @@
+use crate::diagnostic::ErrorFormat;
+
+#[derive(Clone)]
 pub struct Config {
     pub measure_durations: bool,
     pub visualize: bool,
+    pub check_only: bool,
+    pub error_format: ErrorFormat,
+    pub max_call_depth: usize,
+    pub wrapping_arithmetic: bool,
+    pub program_args: Vec<String>,
+    pub list_unused_externs: bool,
 }
 
 impl Config {
@@ -8,6 +17,12 @@ impl Config {
         Config {
             measure_durations: false,
             visualize: false,
+            check_only: false,
+            error_format: ErrorFormat::Text,
+            max_call_depth: 10000,
+            wrapping_arithmetic: false,
+            program_args: Vec::new(),
+            list_unused_externs: false,
         }
     }
 }
@@ -0,0 +1,282 @@
+use colored::*;
+use siko_location_info::file_manager::FileManager;
+use siko_location_info::filepath::FilePath;
+use siko_location_info::location::Location;
+use siko_location_info::location_set::LocationSet;
+use std::cmp;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ErrorFormat {
+    Text,
+    Json,
+}
+
+fn s_from_range(chars: &[char], start: usize, end: usize) -> String {
+    let start = cmp::min(start, end);
+    let subs = &chars[start..end];
+    let s: String = subs.iter().collect();
+    s
+}
+
+fn print_location_set(file_manager: &FileManager, location_set: &LocationSet) {
+    let input = file_manager.content(&location_set.file_path);
+    let lines: Vec<_> = input.lines().collect();
+    let mut first = true;
+    let pipe = "|";
+    let mut last_line = 0;
+    for (line_index, ranges) in &location_set.lines {
+        last_line = *line_index;
+        if first {
+            first = false;
+            eprintln!(
+                "{}{}:{}",
+                "-- ".blue(),
+                location_set.file_path.path.green(),
+                format!("{}", line_index + 1).green()
+            );
+            if *line_index != 0 {
+                let line = &lines[*line_index - 1];
+                eprintln!("{} {}", pipe.blue(), line);
+            }
+        }
+        let line = &lines[*line_index];
+        let chars: Vec<_> = line.chars().collect();
+        let first = s_from_range(&chars[..], 0, ranges[0].start);
+        eprint!("{} {}", pipe.blue(), first);
+        for (index, range) in ranges.iter().enumerate() {
+            let s = s_from_range(&chars[..], range.start, range.end);
+            eprint!("{}", s.yellow());
+            if index < ranges.len() - 1 {
+                let s = s_from_range(&chars[..], range.end, ranges[index + 1].start);
+                eprint!("{}", s);
+            }
+        }
+        let last = s_from_range(&chars[..], ranges[ranges.len() - 1].end, chars.len());
+        eprintln!("{}", last);
+    }
+    if last_line + 1 < lines.len() {
+        let line = &lines[last_line + 1];
+        eprintln!("{} {}", pipe.blue(), line);
+    }
+}
+
+fn print_location(file_manager: &FileManager, file_path: &FilePath, location: &Location) {
+    let input = file_manager.content(file_path);
+    let lines: Vec<_> = input.lines().collect();
+    eprintln!(
+        "--{}:{}",
+        file_path.path.green(),
+        format!("{}", location.line + 1).green()
+    );
+    let line = &lines[location.line];
+    let chars: Vec<_> = line.chars().collect();
+    let first = s_from_range(&chars[..], 0, location.span.start);
+    eprint!("{}", first);
+    let s = s_from_range(&chars[..], location.span.start, location.span.end);
+    eprint!("{}", s.red());
+    let last = s_from_range(&chars[..], location.span.end, chars.len());
+    eprintln!("{}", last);
+}
+
+fn escape_json(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            '\t' => result += "\\t",
+            c if (c as u32) < 0x20 => result += &format!("\\u{:04x}", c as u32),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+fn range_to_json(line: usize, start: usize, end: usize) -> String {
+    format!(
+        "{{\"line\":{},\"column_start\":{},\"column_end\":{}}}",
+        line + 1,
+        start + 1,
+        end + 1
+    )
+}
+
+pub enum DiagnosticPart {
+    Line(String),
+    LocationSet(LocationSet),
+    Location(FilePath, Location),
+}
+
+impl DiagnosticPart {
+    fn location_to_json(&self) -> Option<String> {
+        let (file_path, ranges) = match self {
+            DiagnosticPart::Line(_) => return None,
+            DiagnosticPart::LocationSet(location_set) => {
+                let mut ranges = Vec::new();
+                for (line_index, line_ranges) in &location_set.lines {
+                    for range in line_ranges {
+                        ranges.push(range_to_json(*line_index, range.start, range.end));
+                    }
+                }
+                (&location_set.file_path, ranges)
+            }
+            DiagnosticPart::Location(file_path, location) => {
+                let range = range_to_json(location.line, location.span.start, location.span.end);
+                (file_path, vec![range])
+            }
+        };
+        Some(format!(
+            "{{\"file\":\"{}\",\"ranges\":[{}]}}",
+            escape_json(&file_path.path),
+            ranges.join(",")
+        ))
+    }
+}
+
+// Removes the ANSI escape sequences added by the colored crate
+fn strip_colors(line: &str) -> String {
+    let mut result = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            for c in &mut chars {
+                if c == 'm' {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn strip_error_prefix(line: &str) -> &str {
+    let mut line = line;
+    while line.starts_with("ERROR: ") {
+        line = &line["ERROR: ".len()..];
+    }
+    line
+}
+
+// The name of the error variant
+pub fn get_kind<T: fmt::Debug>(error: &T) -> String {
+    let debug = format!("{:?}", error);
+    debug
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect()
+}
+
+/// A single reported error, kept independent of the output format.
+pub struct Diagnostic {
+    pub stage: String,
+    pub kind: String,
+    pub parts: Vec<DiagnosticPart>,
+}
+
+impl Diagnostic {
+    pub fn new(stage: &str, kind: String) -> Diagnostic {
+        Diagnostic {
+            stage: stage.to_string(),
+            kind: kind,
+            parts: Vec::new(),
+        }
+    }
+
+    pub fn add_line(&mut self, line: String) {
+        self.parts.push(DiagnosticPart::Line(line));
+    }
+
+    pub fn add_location_set(&mut self, location_set: &LocationSet) {
+        self.parts
+            .push(DiagnosticPart::LocationSet(location_set.clone()));
+    }
+
+    pub fn add_location(&mut self, file_path: &FilePath, location: &Location) {
+        self.parts.push(DiagnosticPart::Location(
+            file_path.clone(),
+            location.clone(),
+        ));
+    }
+
+    pub fn print_text(&self, file_manager: &FileManager) {
+        for part in &self.parts {
+            match part {
+                DiagnosticPart::Line(line) => eprintln!("{}", line),
+                DiagnosticPart::LocationSet(location_set) => {
+                    print_location_set(file_manager, location_set)
+                }
+                DiagnosticPart::Location(file_path, location) => {
+                    print_location(file_manager, file_path, location)
+                }
+            }
+        }
+    }
+
+    // The same diagnostic with the colors removed from its lines
+    pub fn without_colors(self) -> Diagnostic {
+        let parts = self
+            .parts
+            .into_iter()
+            .map(|part| match part {
+                DiagnosticPart::Line(line) => DiagnosticPart::Line(strip_colors(&line)),
+                part => part,
+            })
+            .collect();
+        Diagnostic {
+            stage: self.stage,
+            kind: self.kind,
+            parts: parts,
+        }
+    }
+
+    // The first line without colors and the error prefix
+    pub fn get_message(&self) -> String {
+        for part in &self.parts {
+            if let DiagnosticPart::Line(line) = part {
+                return strip_error_prefix(&strip_colors(line)).to_string();
+            }
+        }
+        format!("")
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut lines = Vec::new();
+        let mut locations = Vec::new();
+        for part in &self.parts {
+            match part {
+                DiagnosticPart::Line(line) => {
+                    let line = strip_colors(line);
+                    let line = strip_error_prefix(&line);
+                    lines.push(format!("\"{}\"", escape_json(line)));
+                }
+                _ => {
+                    locations.extend(part.location_to_json());
+                }
+            }
+        }
+        let message = if lines.is_empty() {
+            format!("\"\"")
+        } else {
+            lines.remove(0)
+        };
+        let primary = if locations.is_empty() {
+            format!("null")
+        } else {
+            locations.remove(0)
+        };
+        format!(
+            "{{\"stage\":\"{}\",\"kind\":\"{}\",\"message\":{},\"notes\":[{}],\"location\":{},\"secondary_locations\":[{}]}}",
+            self.stage,
+            self.kind,
+            message,
+            lines.join(","),
+            primary,
+            locations.join(",")
+        )
+    }
+}
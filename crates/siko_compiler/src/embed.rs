@@ -0,0 +1,377 @@
+use crate::compiler::Compiler;
+use crate::compiler::CompilerInput;
+use crate::config::Config;
+use crate::diagnostic::Diagnostic;
+use crate::error::Error;
+use siko_interpreter::convert::FromValue;
+use siko_interpreter::convert::IntoArgs;
+use siko_interpreter::extern_function::ExternRegistry;
+use siko_interpreter::interpreter::Interpreter;
+use siko_ir::function::FunctionId;
+use siko_ir::function::FunctionInfo;
+use siko_ir::types::Type;
+use std::fmt;
+use std::rc::Rc;
+
+pub enum EmbedError {
+    Compile(Vec<Diagnostic>),
+    FunctionNotFound(String),
+    ArgumentCountMismatch(String, usize, usize),
+    NotConcreteType(String, String),
+    TypeMismatch(String, String),
+    Runtime(Vec<Diagnostic>),
+}
+
+impl fmt::Display for EmbedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EmbedError::Compile(diagnostics) | EmbedError::Runtime(diagnostics) => {
+                let messages: Vec<_> = diagnostics.iter().map(|d| d.get_message()).collect();
+                write!(f, "{}", messages.join("\n"))
+            }
+            EmbedError::FunctionNotFound(name) => write!(f, "function {} not found", name),
+            EmbedError::ArgumentCountMismatch(name, expected, found) => write!(
+                f,
+                "function {} takes {} argument(s), {} given",
+                name, expected, found
+            ),
+            EmbedError::NotConcreteType(name, ty) => {
+                write!(f, "function {} has a polymorphic type {}", name, ty)
+            }
+            EmbedError::TypeMismatch(name, ty) => write!(
+                f,
+                "function {} has type {}, which does not match the Rust types",
+                name, ty
+            ),
+        }
+    }
+}
+
+impl fmt::Debug for EmbedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// A function of a compiled program, found by module and name.
+#[derive(Clone)]
+pub struct Function {
+    id: FunctionId,
+    name: String,
+    ty: Type,
+}
+
+/// A compiled program whose functions can be called any number of times.
+/// It is not Send, each program is used on the thread that compiled it,
+/// but any number of programs can be used on the same thread.
+pub struct CompiledProgram {
+    compiler: Compiler,
+    interpreter: Rc<Interpreter>,
+}
+
+impl CompiledProgram {
+    pub fn compile(inputs: Vec<CompilerInput>) -> Result<CompiledProgram, EmbedError> {
+        let registry = Interpreter::create_extern_registry();
+        CompiledProgram::compile_with(Config::new(), inputs, registry)
+    }
+
+    /// The registry must contain the std externs as well, see Interpreter::create_extern_registry.
+    pub fn compile_with(
+        config: Config,
+        inputs: Vec<CompilerInput>,
+        registry: ExternRegistry,
+    ) -> Result<CompiledProgram, EmbedError> {
+        let max_call_depth = config.max_call_depth;
+        let wrapping_arithmetic = config.wrapping_arithmetic;
+        let program_args = config.program_args.clone();
+        let mut compiler = Compiler::new(config);
+        match compiler.compile_program_with_externs(inputs, false, &registry) {
+            Ok(program) => {
+                let interpreter = Interpreter::create(
+                    program,
+                    max_call_depth,
+                    wrapping_arithmetic,
+                    program_args,
+                    registry,
+                );
+                Ok(CompiledProgram {
+                    compiler: compiler,
+                    interpreter: interpreter,
+                })
+            }
+            Err(e) => Err(EmbedError::Compile(compiler.get_diagnostics(e))),
+        }
+    }
+
+    pub fn get_function(&self, module: &str, name: &str) -> Result<Function, EmbedError> {
+        let program = self.interpreter.get_program();
+        for (id, function) in &program.functions.items {
+            if let FunctionInfo::NamedFunction(info) = &function.info {
+                if info.module == module && info.name == name {
+                    let ty = program.function_types.get(id).expect("untyped func");
+                    return Ok(Function {
+                        id: *id,
+                        name: format!("{}.{}", module, name),
+                        ty: ty.clone(),
+                    });
+                }
+            }
+        }
+        Err(EmbedError::FunctionNotFound(format!("{}.{}", module, name)))
+    }
+
+    pub fn get_type_string(&self, function: &Function) -> String {
+        function
+            .ty
+            .get_resolved_type_string(self.interpreter.get_program())
+    }
+
+    /// Calls the function with a tuple of all of its arguments,
+    /// the argument types and R must match the Siko type of the function.
+    pub fn call<A: IntoArgs, R: FromValue>(
+        &self,
+        function: &Function,
+        args: A,
+    ) -> Result<R, EmbedError> {
+        let arg_count = A::get_arg_count();
+        if arg_count != function.ty.get_arg_count() {
+            return Err(EmbedError::ArgumentCountMismatch(
+                function.name.clone(),
+                function.ty.get_arg_count(),
+                arg_count,
+            ));
+        }
+        let mut arg_types = Vec::new();
+        function.ty.get_args(&mut arg_types);
+        let result_type = function.ty.get_result_type(arg_count);
+        if !result_type.is_concrete_type() || arg_types.iter().any(|ty| !ty.is_concrete_type()) {
+            return Err(EmbedError::NotConcreteType(
+                function.name.clone(),
+                self.get_type_string(function),
+            ));
+        }
+        let program = self.interpreter.get_program();
+        if !A::matches_types(program, &arg_types) || !R::matches_type(program, &result_type) {
+            return Err(EmbedError::TypeMismatch(
+                function.name.clone(),
+                self.get_type_string(function),
+            ));
+        }
+        let interpreter = &self.interpreter;
+        let args = args.into_args(interpreter, arg_types);
+        interpreter
+            .call_function(function.id, args)
+            .map(|value| R::from_value(interpreter, value))
+            .map_err(|e| EmbedError::Runtime(self.compiler.get_diagnostics(Error::RuntimeError(e))))
+    }
+
+    pub fn call_by_name<A: IntoArgs, R: FromValue>(
+        &self,
+        module: &str,
+        name: &str,
+        args: A,
+    ) -> Result<R, EmbedError> {
+        let function = self.get_function(module, name)?;
+        self.call(&function, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn collect_std(path: &Path, inputs: &mut Vec<CompilerInput>) {
+        for entry in fs::read_dir(path).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                collect_std(&path, inputs);
+            } else if let Some(ext) = path.extension() {
+                if ext == "sk" {
+                    inputs.push(CompilerInput::File {
+                        name: format!("{}", path.display()),
+                    });
+                }
+            }
+        }
+    }
+
+    fn compile(content: &str) -> Result<CompiledProgram, EmbedError> {
+        compile_with(content, Interpreter::create_extern_registry())
+    }
+
+    fn compile_with(
+        content: &str,
+        registry: ExternRegistry,
+    ) -> Result<CompiledProgram, EmbedError> {
+        let mut inputs = Vec::new();
+        let std_path = format!("{}/../../std", env!("CARGO_MANIFEST_DIR"));
+        collect_std(Path::new(&std_path), &mut inputs);
+        inputs.push(CompilerInput::Memory {
+            name: format!("test.sk"),
+            content: content.to_string(),
+        });
+        CompiledProgram::compile_with(Config::new(), inputs, registry)
+    }
+
+    const PROGRAM: &str = "module Test where
+
+add :: Int -> Int -> Int
+add a b = a + b
+
+divide :: Int -> Int -> Int
+divide a b = a / b
+
+pairs :: [Int] -> [(Int, String)]
+pairs l = List.map (\\x -> (x, show x)) l
+
+count :: Int -> Int
+count n = if n == 0 then 0 else 1 + count (n - 1)
+";
+
+    fn lookup_program(a: i64, b: i64) -> String {
+        format!(
+            "module Test where
+
+import Map
+
+data Key = {{ name :: String }} deriving (PartialEq, Eq, PartialOrd, Ord)
+
+lookup :: String -> Option Int
+lookup name = Map.get (Map.fromList [(Key \"a\", {}), (Key \"b\", {})]) (Key name)
+",
+            a, b
+        )
+    }
+
+    #[test]
+    fn compile_error() {
+        let err = compile("module Test where\n\nfoo = bar\n").err().unwrap();
+        match err {
+            EmbedError::Compile(diagnostics) => assert!(!diagnostics.is_empty()),
+            _ => panic!("unexpected error {}", err),
+        }
+    }
+
+    #[test]
+    fn plain_diagnostics() {
+        let mut diagnostic = Diagnostic::new("test", format!("Test"));
+        diagnostic.add_line(format!(
+            "\u{1b}[31mERROR:\u{1b}[0m unknown \u{1b}[33mfoo\u{1b}[0m"
+        ));
+        assert_eq!(diagnostic.get_message(), "unknown foo");
+        assert!(!diagnostic.to_json().contains('\u{1b}'));
+    }
+
+    #[test]
+    fn call_function() {
+        let program = compile(PROGRAM).unwrap();
+        let sum: i64 = program.call_by_name("Test", "add", (1i64, 2i64)).unwrap();
+        assert_eq!(sum, 3);
+        let pairs: Vec<(i64, String)> = program
+            .call_by_name("Test", "pairs", (vec![1i64, 2i64],))
+            .unwrap();
+        assert_eq!(pairs, vec![(1, format!("1")), (2, format!("2"))]);
+    }
+
+    #[test]
+    fn missing_function() {
+        let program = compile(PROGRAM).unwrap();
+        match program.get_function("Test", "missing") {
+            Err(EmbedError::FunctionNotFound(name)) => assert_eq!(name, "Test.missing"),
+            _ => panic!("missing function found"),
+        }
+    }
+
+    #[test]
+    fn argument_count_mismatch() {
+        let program = compile(PROGRAM).unwrap();
+        let result: Result<i64, _> = program.call_by_name("Test", "add", (1i64,));
+        match result {
+            Err(EmbedError::ArgumentCountMismatch(_, 2, 1)) => {}
+            _ => panic!("partial call accepted"),
+        }
+    }
+
+    #[test]
+    fn type_mismatch() {
+        let program = compile(PROGRAM).unwrap();
+        let result: Result<i64, _> = program.call_by_name("Test", "add", (1i64, format!("2")));
+        match result {
+            Err(EmbedError::TypeMismatch(..)) => {}
+            _ => panic!("wrong argument type accepted"),
+        }
+        let result: Result<String, _> = program.call_by_name("Test", "add", (1i64, 2i64));
+        match result {
+            Err(EmbedError::TypeMismatch(..)) => {}
+            _ => panic!("wrong result type accepted"),
+        }
+    }
+
+    #[test]
+    fn runtime_error() {
+        let program = compile(PROGRAM).unwrap();
+        let result: Result<i64, _> = program.call_by_name("Test", "divide", (1i64, 0i64));
+        match result {
+            Err(EmbedError::Runtime(diagnostics)) => {
+                assert_eq!(diagnostics[0].get_message(), "Division by zero")
+            }
+            _ => panic!("division by zero succeeded"),
+        }
+    }
+
+    #[test]
+    fn deep_recursion() {
+        // the test thread has a small stack, the interpreter must not depend on it
+        let program = compile(PROGRAM).unwrap();
+        let depth: i64 = program.call_by_name("Test", "count", (9000i64,)).unwrap();
+        assert_eq!(depth, 9000);
+    }
+
+    #[test]
+    fn two_programs_in_one_thread() {
+        let first = compile(&lookup_program(1, 2)).unwrap();
+        let second = compile(&lookup_program(10, 20)).unwrap();
+        let value: Option<i64> = first
+            .call_by_name("Test", "lookup", (format!("a"),))
+            .unwrap();
+        assert_eq!(value, Some(1));
+        let value: Option<i64> = second
+            .call_by_name("Test", "lookup", (format!("b"),))
+            .unwrap();
+        assert_eq!(value, Some(20));
+        drop(first);
+        let value: Option<i64> = second
+            .call_by_name("Test", "lookup", (format!("a"),))
+            .unwrap();
+        assert_eq!(value, Some(10));
+        let value: Option<i64> = second
+            .call_by_name("Test", "lookup", (format!("c"),))
+            .unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn nested_evaluation() {
+        let inner = compile(&lookup_program(1, 2)).unwrap();
+        let mut registry = Interpreter::create_extern_registry();
+        registry.add_native_function("Test", "inner", move |name: String| {
+            let value: Option<i64> = inner.call_by_name("Test", "lookup", (name,)).unwrap();
+            value.unwrap_or(0)
+        });
+        let outer = "module Test where
+
+inner :: String -> Int
+inner name = extern
+
+outer :: String -> Int
+outer name = inner name + 100
+";
+        let outer = compile_with(outer, registry).unwrap();
+        let value: i64 = outer
+            .call_by_name("Test", "outer", (format!("b"),))
+            .unwrap();
+        assert_eq!(value, 102);
+    }
+}
@@ -1,10 +1,9 @@
+use crate::diagnostic::get_kind;
+use crate::diagnostic::Diagnostic;
 use colored::*;
+use siko_interpreter::error::RuntimeError;
 use siko_location_info::error_context::ErrorContext;
-use siko_location_info::file_manager::FileManager;
-use siko_location_info::filepath::FilePath;
-use siko_location_info::location::Location;
 use siko_location_info::location_id::LocationId;
-use siko_location_info::location_set::LocationSet;
 use siko_name_resolver::error::Error as ResolverErrorContainer;
 use siko_name_resolver::error::ResolverError;
 use siko_parser::error::LexerError;
@@ -12,57 +11,14 @@ use siko_parser::error::ParseError;
 use siko_type_checker::error::Error as TypecheckErrorContainer;
 use siko_type_checker::error::TypecheckError;
 use siko_util::format_list;
-use std::cmp;
 use std::convert::From;
 use std::io::Error as IoError;
 
-fn s_from_range(chars: &[char], start: usize, end: usize) -> String {
-    let start = cmp::min(start, end);
-    let subs = &chars[start..end];
-    let s: String = subs.iter().collect();
-    s
-}
+const MAX_REPORTED_FRAMES: usize = 20;
 
-fn print_location_set(file_manager: &FileManager, location_set: &LocationSet) {
-    let input = file_manager.content(&location_set.file_path);
-    let lines: Vec<_> = input.lines().collect();
-    let mut first = true;
-    let pipe = "|";
-    let mut last_line = 0;
-    for (line_index, ranges) in &location_set.lines {
-        last_line = *line_index;
-        if first {
-            first = false;
-            eprintln!(
-                "{}{}:{}",
-                "-- ".blue(),
-                location_set.file_path.path.green(),
-                format!("{}", line_index + 1).green()
-            );
-            if *line_index != 0 {
-                let line = &lines[*line_index - 1];
-                eprintln!("{} {}", pipe.blue(), line);
-            }
-        }
-        let line = &lines[*line_index];
-        let chars: Vec<_> = line.chars().collect();
-        let first = s_from_range(&chars[..], 0, ranges[0].start);
-        eprint!("{} {}", pipe.blue(), first);
-        for (index, range) in ranges.iter().enumerate() {
-            let s = s_from_range(&chars[..], range.start, range.end);
-            eprint!("{}", s.yellow());
-            if index < ranges.len() - 1 {
-                let s = s_from_range(&chars[..], range.end, ranges[index + 1].start);
-                eprint!("{}", s);
-            }
-        }
-        let last = s_from_range(&chars[..], ranges[ranges.len() - 1].end, chars.len());
-        eprintln!("{}", last);
-    }
-    if last_line + 1 < lines.len() {
-        let line = &lines[last_line + 1];
-        eprintln!("{} {}", pipe.blue(), line);
-    }
+#[derive(Debug)]
+pub enum ExternError {
+    UnimplementedExternFunction(String, LocationId),
 }
 
 #[derive(Debug)]
@@ -72,200 +28,194 @@ pub enum Error {
     ParseError(ParseError),
     ResolverError(ResolverErrorContainer),
     TypecheckError(TypecheckErrorContainer),
-    RuntimeError(String, LocationId),
+    ExternError(Vec<ExternError>),
+    RuntimeError(RuntimeError),
 }
 
 impl Error {
-    fn report_location(file_manager: &FileManager, file_path: &FilePath, location: &Location) {
-        let input = file_manager.content(file_path);
-        let lines: Vec<_> = input.lines().collect();
-        eprintln!(
-            "--{}:{}",
-            file_path.path.green(),
-            format!("{}", location.line + 1).green()
-        );
-        let line = &lines[location.line];
-        let chars: Vec<_> = line.chars().collect();
-        let first = s_from_range(&chars[..], 0, location.span.start);
-        eprint!("{}", first);
-        let s = s_from_range(&chars[..], location.span.start, location.span.end);
-        eprint!("{}", s.red());
-        let last = s_from_range(&chars[..], location.span.end, chars.len());
-        eprintln!("{}", last);
+    pub fn report_error(&self, context: &ErrorContext) {
+        for diagnostic in self.get_diagnostics(context) {
+            diagnostic.print_text(&context.file_manager);
+        }
     }
 
-    fn report_error_base(
-        msg: &str,
-        file_manager: &FileManager,
-        file_path: &FilePath,
-        location: &Location,
-    ) {
-        let error = "ERROR:";
-        eprintln!("{} {}", error.red(), msg);
-        Error::report_location(file_manager, file_path, location);
+    pub fn report_error_json(&self, context: &ErrorContext) {
+        for diagnostic in self.get_diagnostics(context) {
+            eprintln!("{}", diagnostic.to_json());
+        }
     }
 
-    pub fn report_error(&self, context: &ErrorContext) {
-        let file_manager = &context.file_manager;
+    pub fn get_diagnostics(&self, context: &ErrorContext) -> Vec<Diagnostic> {
         let location_info = &context.location_info;
         let error = "ERROR:";
+        let mut diagnostics = Vec::new();
         match self {
+            Error::IoError(err) => {
+                let mut diagnostic = Diagnostic::new("io", format!("{:?}", err.kind()));
+                diagnostic.add_line(format!("{} {}", error.red(), err));
+                diagnostics.push(diagnostic);
+            }
             Error::LexerError(errors) => {
                 for err in errors {
+                    let mut diagnostic = Diagnostic::new("lexer", get_kind(err));
                     match err {
                         LexerError::General(msg, file_path, location) => {
-                            Error::report_error_base(msg, file_manager, file_path, location);
+                            diagnostic.add_line(format!("{} {}", error.red(), msg));
+                            diagnostic.add_location(file_path, location);
                         }
                         LexerError::UnsupportedCharacter(c, location) => {
-                            Error::report_error_base(
-                                &format!(
-                                    "{} unsupported character {}",
-                                    error.red(),
-                                    format!("{}", c).yellow()
-                                ),
-                                file_manager,
-                                &location.file_path,
-                                &location.location,
-                            );
+                            diagnostic.add_line(format!(
+                                "{} unsupported character {}",
+                                error.red(),
+                                format!("{}", c).yellow()
+                            ));
+                            diagnostic.add_location(&location.file_path, &location.location);
                         }
                     }
+                    diagnostics.push(diagnostic);
                 }
             }
             Error::ParseError(err) => {
-                Error::report_error_base(&err.msg, file_manager, &err.file_path, &err.location);
+                let mut diagnostic = Diagnostic::new("parser", get_kind(err));
+                diagnostic.add_line(format!("{} {}", error.red(), err.msg));
+                diagnostic.add_location(&err.file_path, &err.location);
+                diagnostics.push(diagnostic);
             }
             Error::ResolverError(errs) => {
                 for err in &errs.errors {
+                    let mut diagnostic = Diagnostic::new("resolver", get_kind(err));
                     match err {
                         ResolverError::ModuleConflict(errors) => {
                             for (name, ids) in errors.iter() {
-                                eprintln!(
+                                diagnostic.add_line(format!(
                                     "{} module name {} defined more than once",
                                     error.red(),
                                     name.yellow()
-                                );
+                                ));
                                 for id in ids.iter() {
-                                    let location_set = location_info.get_item_location(id);
-                                    print_location_set(file_manager, location_set);
+                                    diagnostic
+                                        .add_location_set(location_info.get_item_location(id));
                                 }
                             }
                         }
                         ResolverError::ImportedModuleNotFound(name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} imported module {} does not exist",
                                 error.red(),
                                 name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::UnknownTypeName(var_name, id) => {
-                            eprintln!("{} unknown type name {}", error.red(), var_name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!(
+                                "{} unknown type name {}",
+                                error.red(),
+                                var_name.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::UnknownTypeArg(var_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} unknown type argument {}",
                                 error.red(),
                                 var_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::TypeArgumentConflict(args, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} type argument(s) are not unique: {}",
                                 error.red(),
                                 format_list(args).yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::ArgumentConflict(args, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} argument(s) are not unique: {}",
                                 error.red(),
                                 format_list(args).yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::LambdaArgumentConflict(args, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} lambda argument(s) {} are not unique",
                                 error.red(),
                                 format_list(args).yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::UnknownFunction(var_name, id) => {
-                            eprintln!("{} unknown function {}", error.red(), var_name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!(
+                                "{} unknown function {}",
+                                error.red(),
+                                var_name.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::AmbiguousName(var_name, id) => {
-                            eprintln!("{} ambiguous name {}", error.red(), var_name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!(
+                                "{} ambiguous name {}",
+                                error.red(),
+                                var_name.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::UnusedTypeArgument(arg, id) => {
-                            eprintln!("{} unused type argument: {}", error.red(), arg.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!(
+                                "{} unused type argument: {}",
+                                error.red(),
+                                arg.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::InternalModuleConflicts(module_name, name, locations) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} conflicting items named {} in module {}",
                                 error.red(),
                                 name.yellow(),
                                 module_name.yellow()
-                            );
+                            ));
                             for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
+                                diagnostic.add_location_set(location_info.get_item_location(id));
                             }
                         }
                         ResolverError::RecordFieldNotUnique(record_name, item_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} field name {} is not unique in record {}",
                                 error.red(),
                                 item_name.yellow(),
                                 record_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::VariantNotUnique(adt_name, variant_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} variant name {} is not unique in type {}",
                                 error.red(),
                                 variant_name.yellow(),
                                 adt_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::ExportNoMatch(module_name, entity_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} item {} does not export anything in module {}",
                                 error.red(),
                                 entity_name.yellow(),
                                 module_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::ImportNoMatch(module_name, entity_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} item {} does not import anything from module {}",
                                 error.red(),
                                 entity_name.yellow(),
                                 module_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::IncorrectTypeArgumentCount(
                             type_name,
@@ -273,212 +223,207 @@ impl Error {
                             found,
                             id,
                         ) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} incorrect type argument count for type {}",
                                 error.red(),
                                 type_name.yellow(),
-                            );
+                            ));
                             let expected = format!("{}", expected);
                             let found = format!("{}", found);
-                            eprintln!("Expected: {}", expected.yellow());
-                            eprintln!("Found:    {}", found.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!("Expected: {}", expected.yellow()));
+                            diagnostic.add_line(format!("Found:    {}", found.yellow()));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::NameNotType(name, id) => {
-                            eprintln!("{} name is not a type {}", error.red(), name.yellow(),);
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!(
+                                "{} name is not a type {}",
+                                error.red(),
+                                name.yellow(),
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::UnusedHiddenItem(hidden_item, module_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} hidden item {} does not hide anything from module {}",
                                 error.red(),
                                 hidden_item.yellow(),
                                 module_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::UnknownFieldName(field_name, id) => {
-                            eprintln!("{} unknown field name {}", error.red(), field_name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!(
+                                "{} unknown field name {}",
+                                error.red(),
+                                field_name.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::NotIrrefutablePattern(id) => {
-                            eprintln!("{} not irrefutable pattern", error.red(),);
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic
+                                .add_line(format!("{} not irrefutable pattern", error.red(),));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::NotRecordType(name, id) => {
-                            eprintln!("{} {} is not a record type", error.red(), name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!(
+                                "{} {} is not a record type",
+                                error.red(),
+                                name.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::NoSuchField(record, field_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} there is no field named {} in {}",
                                 error.red(),
                                 field_name.yellow(),
                                 record.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::MissingFields(missing_fields, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} missing initialization of the following field(s): {}",
                                 error.red(),
                                 format_list(missing_fields).yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::FieldsInitializedMultipleTimes(
                             fields_initialized_twice,
                             id,
                         ) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} the following field(s) are initialized multiple times: {}",
                                 error.red(),
                                 format_list(fields_initialized_twice).yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::NoRecordFoundWithFields(fields, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} no record found that has all the following field(s): {}",
                                 error.red(),
                                 format_list(fields).yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::NotAClassName(name, id) => {
-                            eprintln!("{} {} is not a class", error.red(), name.yellow(),);
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!(
+                                "{} {} is not a class",
+                                error.red(),
+                                name.yellow(),
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::InvalidArgumentInTypeClassConstraint(arg, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} class constraint argument {} is unknown type argument",
                                 error.red(),
                                 arg.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::NotAClassMember(member_name, class_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} {} is not a member of class {}",
                                 error.red(),
                                 member_name.yellow(),
                                 class_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::MissingClassMemberInInstance(
                             member_name,
                             class_name,
                             id,
                         ) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} class member {} of class {} is missing in instance",
                                 error.red(),
                                 member_name.yellow(),
                                 class_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::ClassMemberTypeArgMissing(member_name, class_arg, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} type arguments of class member {} does not contain the type argument of class: {}",
                                 error.red(),
                                 member_name.yellow(),
                                 class_arg.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::ExtraConstraintInClassMember(member_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} extra type constraint in class member {}",
                                 error.red(),
                                 member_name.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::ConflictingDefaultClassMember(class, name, locations) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} conflicting default implementations for class member {} in class {}",
                                 error.red(),
                                 name.yellow(),
                                 class.yellow()
-                            );
+                            ));
                             for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
+                                diagnostic.add_location_set(location_info.get_item_location(id));
                             }
                         }
                         ResolverError::DefaultClassMemberWithoutType(class, name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} class member {} in class {} has no type signature",
                                 error.red(),
                                 name.yellow(),
                                 class.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::ConflictingFunctionTypesInModule(
                             module,
                             name,
                             locations,
                         ) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} conflicting function types named {} in module {}",
                                 error.red(),
                                 name.yellow(),
                                 module.yellow()
-                            );
+                            ));
                             for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
+                                diagnostic.add_location_set(location_info.get_item_location(id));
                             }
                         }
                         ResolverError::InstanceMemberWithoutImplementation(name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} instance member {} has no implementation",
                                 error.red(),
                                 name.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::ConflictingInstanceMemberFunction(name, locations) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} conflicting instance member function named {}",
                                 error.red(),
                                 name.yellow(),
-                            );
+                            ));
                             for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
+                                diagnostic.add_location_set(location_info.get_item_location(id));
                             }
                         }
                         ResolverError::ConflictingFunctionTypesInInstance(name, locations) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} conflicting function types named {} in instance",
                                 error.red(),
                                 name.yellow(),
-                            );
+                            ));
                             for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
+                                diagnostic.add_location_set(location_info.get_item_location(id));
                             }
                         }
                         ResolverError::FunctionTypeWithoutImplementationInModule(
@@ -486,110 +431,191 @@ impl Error {
                             name,
                             id,
                         ) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} function type {} has no implementation in module {}",
                                 error.red(),
                                 name.yellow(),
                                 module.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::InvalidClassArgument(id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} invalid class argument, must be a single type argument",
                                 error.red(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::InvalidTypeArgInInstanceConstraint(arg, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} instance constraint argument {} is unknown type argument",
                                 error.red(),
                                 arg.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::NamedInstancedNotUnique(module, instance, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "named instance {} is not unique in module {}",
                                 instance.yellow(),
                                 module.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         ResolverError::PatternBindConflict(name, ids) => {
-                            eprintln!("Multiple variable named {} found", name.yellow(),);
+                            diagnostic.add_line(format!(
+                                "Multiple variable named {} found",
+                                name.yellow(),
+                            ));
                             for id in ids {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
+                                diagnostic.add_location_set(location_info.get_item_location(id));
                             }
                         }
                         ResolverError::PatternBindNotPresent(name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "Variable {} not present in all patterns in or pattern",
                                 name.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                        }
+                        ResolverError::UnknownProtocol(name, id) => {
+                            diagnostic.add_line(format!(
+                                "{} unknown protocol {}",
+                                error.red(),
+                                name.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                        }
+                        ResolverError::UnknownProtocolHandler(name, id) => {
+                            diagnostic.add_line(format!(
+                                "{} unknown protocol handler function {}",
+                                error.red(),
+                                name.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                        }
+                        ResolverError::ConflictingProtocolHandlers(actor, protocol, locations) => {
+                            diagnostic.add_line(format!(
+                                "{} conflicting handlers for protocol {} in actor {}",
+                                error.red(),
+                                protocol.yellow(),
+                                actor.yellow()
+                            ));
+                            for id in locations {
+                                diagnostic.add_location_set(location_info.get_item_location(id));
+                            }
+                        }
+                        ResolverError::FunctionClauseArgumentMismatch(
+                            name,
+                            expected,
+                            found,
+                            id,
+                        ) => {
+                            diagnostic.add_line(format!(
+                                "{} clause of function {} has {} argument(s), expected {}",
+                                error.red(),
+                                name.yellow(),
+                                format!("{}", found).yellow(),
+                                format!("{}", expected).yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                     }
+                    diagnostics.push(diagnostic);
                 }
             }
-            Error::RuntimeError(err, id) => {
-                eprintln!("{} {}", error.red(), err);
-                let location_set = location_info.get_item_location(id);
-                print_location_set(file_manager, location_set);
+            Error::ExternError(errors) => {
+                for err in errors {
+                    let mut diagnostic = Diagnostic::new("extern", get_kind(err));
+                    match err {
+                        ExternError::UnimplementedExternFunction(name, id) => {
+                            diagnostic.add_line(format!(
+                                "{} extern function {} has no implementation",
+                                error.red(),
+                                name.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                        }
+                    }
+                    diagnostics.push(diagnostic);
+                }
+            }
+            Error::RuntimeError(err) => {
+                let mut diagnostic = Diagnostic::new("runtime", format!("RuntimeError"));
+                diagnostic.add_line(format!("{} {}", error.red(), err.message));
+                if let Some(id) = &err.location_id {
+                    diagnostic.add_location_set(location_info.get_item_location(id));
+                }
+                if !err.stack.is_empty() {
+                    diagnostic.add_line(format!("Call stack:"));
+                }
+                for frame in err.stack.iter().take(MAX_REPORTED_FRAMES) {
+                    match &frame.call_location {
+                        Some(id) => {
+                            let location_set = location_info.get_item_location(id);
+                            let line = location_set.lines.keys().next().cloned().unwrap_or(0);
+                            diagnostic.add_line(format!(
+                                "    {} called at {}:{}",
+                                frame.function.yellow(),
+                                location_set.file_path.path,
+                                line + 1
+                            ));
+                        }
+                        None => {
+                            diagnostic.add_line(format!("    {}", frame.function.yellow()));
+                        }
+                    }
+                }
+                if err.stack.len() > MAX_REPORTED_FRAMES {
+                    diagnostic.add_line(format!(
+                        "    ... {} more frame(s)",
+                        err.stack.len() - MAX_REPORTED_FRAMES
+                    ));
+                }
+                diagnostics.push(diagnostic);
             }
             Error::TypecheckError(errs) => {
                 for err in &errs.errors {
+                    let mut diagnostic = Diagnostic::new("typechecker", get_kind(err));
                     match err {
                         TypecheckError::ConflictingInstances(name, id1, id2) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} conflicting class instances for class {}",
                                 error.red(),
                                 name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id1);
-                            print_location_set(file_manager, location_set);
-                            let location_set = location_info.get_item_location(id2);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id1));
+                            diagnostic.add_location_set(location_info.get_item_location(id2));
                         }
                         TypecheckError::DeriveFailureNoInstanceFound(type_name, class_name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} auto derive failure, no instance found for class {} for a member of {}",
                                 error.red(),
                                 class_name.yellow(),
                                 type_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::DeriveFailureInstanceNotGeneric(
                             type_name,
                             class_name,
                             id,
                         ) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} auto derive failure, instance not generic for class {} for a member of {}",
                                 error.red(),
                                 class_name.yellow(),
                                 type_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::UntypedExternFunction(name, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} extern function {} does not have a type signature",
                                 error.red(),
                                 name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::FunctionArgAndSignatureMismatch(
                             name,
@@ -599,111 +625,158 @@ impl Error {
                             is_member,
                         ) => {
                             if *is_member {
-                                eprintln!(
+                                diagnostic.add_line(format!(
                                 "{} member function type signature of {} does not match its argument count",
                                 error.red(),
                                 name.yellow()
-                            );
+                            ));
                             } else {
-                                eprintln!(
+                                diagnostic.add_line(format!(
                                 "{} function type signature of {} does not match its argument count",
                                 error.red(),
                                 name.yellow()
-                            );
+                            ));
                             }
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "Arguments:                      {}",
                                 format!("{}", arg_count).yellow()
-                            );
-                            eprintln!(
+                            ));
+                            diagnostic.add_line(format!(
                                 "Arguments in type signature:    {}",
                                 format!("{}", signature_arg_count).yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::MainNotFound => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} {} function in module {} not found",
                                 error.red(),
                                 "main".yellow(),
                                 "Main".yellow()
-                            );
+                            ));
                         }
                         TypecheckError::TypeMismatch(id, expected, found) => {
-                            eprintln!("{} type mismatch in expression", error.red());
-                            eprintln!("Expected: {}", expected.yellow());
-                            eprintln!("Found:    {}", found.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic
+                                .add_line(format!("{} type mismatch in expression", error.red()));
+                            diagnostic.add_line(format!("Expected: {}", expected.yellow()));
+                            diagnostic.add_line(format!("Found:    {}", found.yellow()));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::FunctionArgumentMismatch(id, args, func) => {
-                            eprintln!("{} invalid argument(s)", error.red());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                            eprintln!("Argument(s):      {}", args.yellow());
-                            eprintln!("Function type:    {}", func.yellow());
+                            diagnostic.add_line(format!("{} invalid argument(s)", error.red()));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                            diagnostic.add_line(format!("Argument(s):      {}", args.yellow()));
+                            diagnostic.add_line(format!("Function type:    {}", func.yellow()));
                         }
                         TypecheckError::InvalidVariantPattern(id, name, expected, found) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} invalid {} variant pattern, argument count mismatch",
                                 error.red(),
                                 name.yellow()
-                            );
-                            eprintln!("Expected:      {}", format!("{}", expected).yellow());
-                            eprintln!("Found:         {}", format!("{}", found).yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_line(format!(
+                                "Expected:      {}",
+                                format!("{}", expected).yellow()
+                            ));
+                            diagnostic.add_line(format!(
+                                "Found:         {}",
+                                format!("{}", found).yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::InvalidRecordPattern(id, name, expected, found) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} invalid {} record pattern, argument count mismatch",
                                 error.red(),
                                 name.yellow()
-                            );
-                            eprintln!("Expected:      {}", format!("{}", expected).yellow());
-                            eprintln!("Found:         {}", format!("{}", found).yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_line(format!(
+                                "Expected:      {}",
+                                format!("{}", expected).yellow()
+                            ));
+                            diagnostic.add_line(format!(
+                                "Found:         {}",
+                                format!("{}", found).yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::TypeAnnotationNeeded(id) => {
-                            eprintln!("{} Type annotation needed", error.red());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!("{} Type annotation needed", error.red()));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::InvalidFormatString(id) => {
-                            eprintln!("{} invalid format string", error.red());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!("{} invalid format string", error.red()));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::CyclicClassDependencies(id, path) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} cyclic class dependencies: {}",
                                 error.red(),
                                 path.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::MissingInstance(class, id) => {
-                            eprintln!("{} missing instance of {}", error.red(), class.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            diagnostic.add_line(format!(
+                                "{} missing instance of {}",
+                                error.red(),
+                                class.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                         TypecheckError::ClassNotAutoDerivable(class, id) => {
-                            eprintln!(
+                            diagnostic.add_line(format!(
                                 "{} class {} is not auto derivable",
                                 error.red(),
                                 class.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                        }
+                        TypecheckError::InvalidProtocolHandler(name, expected, found, id) => {
+                            diagnostic.add_line(format!(
+                                "{} invalid type for protocol handler {}",
+                                error.red(),
+                                name.yellow()
+                            ));
+                            diagnostic.add_line(format!("Expected: {}", expected.yellow()));
+                            diagnostic.add_line(format!("Found:    {}", found.yellow()));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                        }
+                        TypecheckError::NotAnActor(ty, id) => {
+                            diagnostic.add_line(format!(
+                                "{} cannot spawn a value of type {}, it is not an actor",
+                                error.red(),
+                                ty.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                        }
+                        TypecheckError::UnhandledMessage(actor, ty, id) => {
+                            diagnostic.add_line(format!(
+                                "{} actor {} has no handler for message of type {}",
+                                error.red(),
+                                actor.yellow(),
+                                ty.yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                        }
+                        TypecheckError::NonExhaustivePatterns(id, missing) => {
+                            diagnostic.add_line(format!(
+                                "{} non exhaustive patterns, missing: {}",
+                                error.red(),
+                                missing.join(", ").yellow()
+                            ));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
+                        }
+                        TypecheckError::UnreachableCaseArm(id) => {
+                            diagnostic.add_line(format!("{} unreachable case arm", error.red()));
+                            diagnostic.add_location_set(location_info.get_item_location(id));
                         }
                     }
+                    diagnostics.push(diagnostic);
                 }
             }
-            _ => unimplemented!(),
         }
+        diagnostics
     }
 }
 
@@ -730,3 +803,9 @@ impl From<TypecheckErrorContainer> for Error {
         Error::TypecheckError(e)
     }
 }
+
+impl From<RuntimeError> for Error {
+    fn from(e: RuntimeError) -> Error {
+        Error::RuntimeError(e)
+    }
+}
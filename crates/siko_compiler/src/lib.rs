@@ -1,3 +1,5 @@
 pub mod compiler;
 pub mod config;
+pub mod diagnostic;
+pub mod embed;
 pub mod error;
@@ -4,6 +4,7 @@ pub enum BuiltinOperator {
     Sub,
     Mul,
     Div,
+    Rem,
     PipeForward,
     And,
     Or,
@@ -26,6 +27,7 @@ impl BuiltinOperator {
             BuiltinOperator::Sub => format!("Std.Ops.opSub"),
             BuiltinOperator::Mul => format!("Std.Ops.opMul"),
             BuiltinOperator::Div => format!("Std.Ops.opDiv"),
+            BuiltinOperator::Rem => format!("Std.Ops.opRem"),
             BuiltinOperator::Equals => format!("Std.Ops.opEq"),
             BuiltinOperator::NotEquals => format!("Std.Ops.opNotEq"),
             BuiltinOperator::LessThan => format!("Std.Ops.opLessThan"),
@@ -35,6 +37,7 @@ impl BuiltinOperator {
             BuiltinOperator::And => format!("Std.Ops.opAnd"),
             BuiltinOperator::Or => format!("Std.Ops.opOr"),
             BuiltinOperator::Not => format!("Std.Ops.opNot"),
+            BuiltinOperator::Minus => format!("Std.Ops.opNeg"),
             _ => panic!("Op {:?} has no func name", self),
         }
     }
@@ -54,13 +57,24 @@ pub const RESULT_MODULE_NAME: &str = "Result";
 pub const RESULT_TYPE_NAME: &str = "Result";
 pub const MAP_MODULE_NAME: &str = "Map";
 pub const MAP_TYPE_NAME: &str = "Map";
+pub const SET_MODULE_NAME: &str = "Set";
+pub const SET_TYPE_NAME: &str = "Set";
 pub const ORDERING_MODULE_NAME: &str = "Ordering";
 pub const ORDERING_TYPE_NAME: &str = "Ordering";
 pub const STRING_MODULE_NAME: &str = "String";
 pub const STRING_TYPE_NAME: &str = "String";
+pub const CHAR_MODULE_NAME: &str = "Char";
+pub const CHAR_TYPE_NAME: &str = "Char";
 pub const LIST_MODULE_NAME: &str = "List";
 pub const LIST_TYPE_NAME: &str = "List";
+pub const ACTOR_MODULE_NAME: &str = "Std.Actor";
+pub const ENV_MODULE_NAME: &str = "Std.Env";
+pub const IO_FILE_MODULE_NAME: &str = "Std.IO.File";
+pub const IO_ERROR_TYPE_NAME: &str = "IOError";
+pub const PID_TYPE_NAME: &str = "Pid";
 
 pub fn get_qualified_list_type_name() -> String {
     format!("{}.{}", LIST_MODULE_NAME, LIST_TYPE_NAME)
 }
+pub const ENUM_FROM_TO: &str = "Std.Ops.enumFromTo";
+pub const ENUM_FROM_THEN_TO: &str = "Std.Ops.enumFromThenTo";
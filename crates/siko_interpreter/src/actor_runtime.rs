@@ -0,0 +1,67 @@
+use crate::value::Value;
+use siko_ir::actor::ActorId;
+use siko_ir::function::FunctionId;
+use std::collections::VecDeque;
+
+pub struct Message {
+    pub handler_func: FunctionId,
+    pub payload: Value,
+}
+
+pub struct ActorInstance {
+    pub actor_id: ActorId,
+    pub state: Value,
+    pub mailbox: VecDeque<Message>,
+}
+
+pub struct ActorRuntime {
+    instances: Vec<ActorInstance>,
+    next_pid: usize,
+}
+
+impl ActorRuntime {
+    pub fn new() -> ActorRuntime {
+        ActorRuntime {
+            instances: Vec::new(),
+            next_pid: 0,
+        }
+    }
+
+    pub fn spawn(&mut self, actor_id: ActorId, state: Value) -> usize {
+        let pid = self.instances.len();
+        let instance = ActorInstance {
+            actor_id: actor_id,
+            state: state,
+            mailbox: VecDeque::new(),
+        };
+        self.instances.push(instance);
+        pid
+    }
+
+    pub fn get_actor_id(&self, pid: usize) -> Option<ActorId> {
+        self.instances.get(pid).map(|instance| instance.actor_id)
+    }
+
+    pub fn send(&mut self, pid: usize, message: Message) {
+        self.instances[pid].mailbox.push_back(message);
+    }
+
+    // Actors are visited in spawn order, one message per actor at a time,
+    // so the order of handler invocations only depends on the program.
+    pub fn next_message(&mut self) -> Option<(usize, Value, Message)> {
+        let count = self.instances.len();
+        for offset in 0..count {
+            let pid = (self.next_pid + offset) % count;
+            let instance = &mut self.instances[pid];
+            if let Some(message) = instance.mailbox.pop_front() {
+                self.next_pid = (pid + 1) % count;
+                return Some((pid, instance.state.clone(), message));
+            }
+        }
+        None
+    }
+
+    pub fn update_state(&mut self, pid: usize, state: Value) {
+        self.instances[pid].state = state;
+    }
+}
@@ -0,0 +1,555 @@
+use crate::interpreter::Interpreter;
+use crate::util::create_err;
+use crate::util::create_none;
+use crate::util::create_ok;
+use crate::util::create_some;
+use crate::value::Value;
+use crate::value::ValueCore;
+use im_rc::OrdMap;
+use im_rc::Vector;
+use siko_constants::BOOL_MODULE_NAME;
+use siko_constants::BOOL_TYPE_NAME;
+use siko_constants::CHAR_MODULE_NAME;
+use siko_constants::CHAR_TYPE_NAME;
+use siko_constants::FLOAT_MODULE_NAME;
+use siko_constants::FLOAT_TYPE_NAME;
+use siko_constants::INT_MODULE_NAME;
+use siko_constants::INT_TYPE_NAME;
+use siko_constants::LIST_MODULE_NAME;
+use siko_constants::LIST_TYPE_NAME;
+use siko_constants::MAP_MODULE_NAME;
+use siko_constants::MAP_TYPE_NAME;
+use siko_constants::OPTION_MODULE_NAME;
+use siko_constants::OPTION_TYPE_NAME;
+use siko_constants::RESULT_MODULE_NAME;
+use siko_constants::RESULT_TYPE_NAME;
+use siko_constants::STRING_MODULE_NAME;
+use siko_constants::STRING_TYPE_NAME;
+use siko_ir::program::Program;
+use siko_ir::types::Type;
+use std::collections::BTreeMap;
+
+/// A Rust type which corresponds to a set of Siko types.
+pub trait MatchesType {
+    fn matches_type(program: &Program, ty: &Type) -> bool;
+}
+
+// Returns the type arguments if ty is the given named type.
+fn get_named_type_args<'a>(
+    program: &Program,
+    ty: &'a Type,
+    module: &str,
+    name: &str,
+) -> Option<&'a Vec<Type>> {
+    match ty {
+        Type::Named(_, id, args) if *id == program.get_named_type(module, name) => Some(args),
+        _ => None,
+    }
+}
+
+/// Converts an interpreter value into a Rust value.
+/// The type checker guarantees the shape of the value, a mismatch is a bug.
+pub trait FromValue: MatchesType + Sized {
+    fn from_value(interpreter: &Interpreter, value: Value) -> Self;
+}
+
+/// Converts a Rust value into an interpreter value of the given concrete type.
+pub trait IntoValue: MatchesType {
+    fn into_value(self, interpreter: &Interpreter, ty: Type) -> Value;
+}
+
+impl MatchesType for Value {
+    fn matches_type(_: &Program, _: &Type) -> bool {
+        true
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(_: &Interpreter, value: Value) -> Value {
+        value
+    }
+}
+
+impl IntoValue for Value {
+    fn into_value(self, _: &Interpreter, _: Type) -> Value {
+        self
+    }
+}
+
+impl MatchesType for i64 {
+    fn matches_type(program: &Program, ty: &Type) -> bool {
+        get_named_type_args(program, ty, INT_MODULE_NAME, INT_TYPE_NAME).is_some()
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(_: &Interpreter, value: Value) -> i64 {
+        value.core.as_int()
+    }
+}
+
+impl IntoValue for i64 {
+    fn into_value(self, _: &Interpreter, ty: Type) -> Value {
+        Value::new(ValueCore::Int(self), ty)
+    }
+}
+
+impl MatchesType for f64 {
+    fn matches_type(program: &Program, ty: &Type) -> bool {
+        get_named_type_args(program, ty, FLOAT_MODULE_NAME, FLOAT_TYPE_NAME).is_some()
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(_: &Interpreter, value: Value) -> f64 {
+        value.core.as_float()
+    }
+}
+
+impl IntoValue for f64 {
+    fn into_value(self, _: &Interpreter, ty: Type) -> Value {
+        Value::new(ValueCore::Float(self), ty)
+    }
+}
+
+impl MatchesType for String {
+    fn matches_type(program: &Program, ty: &Type) -> bool {
+        get_named_type_args(program, ty, STRING_MODULE_NAME, STRING_TYPE_NAME).is_some()
+    }
+}
+
+impl FromValue for String {
+    fn from_value(_: &Interpreter, value: Value) -> String {
+        value.core.as_string()
+    }
+}
+
+impl IntoValue for String {
+    fn into_value(self, _: &Interpreter, ty: Type) -> Value {
+        Value::new(ValueCore::String(self), ty)
+    }
+}
+
+impl MatchesType for char {
+    fn matches_type(program: &Program, ty: &Type) -> bool {
+        get_named_type_args(program, ty, CHAR_MODULE_NAME, CHAR_TYPE_NAME).is_some()
+    }
+}
+
+impl FromValue for char {
+    fn from_value(_: &Interpreter, value: Value) -> char {
+        value.core.as_char()
+    }
+}
+
+impl IntoValue for char {
+    fn into_value(self, _: &Interpreter, ty: Type) -> Value {
+        Value::new(ValueCore::Char(self), ty)
+    }
+}
+
+impl MatchesType for bool {
+    fn matches_type(program: &Program, ty: &Type) -> bool {
+        get_named_type_args(program, ty, BOOL_MODULE_NAME, BOOL_TYPE_NAME).is_some()
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(_: &Interpreter, value: Value) -> bool {
+        value.core.as_bool()
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value(self, interpreter: &Interpreter, _: Type) -> Value {
+        interpreter.get_bool_value(self)
+    }
+}
+
+impl MatchesType for () {
+    fn matches_type(_: &Program, ty: &Type) -> bool {
+        match ty {
+            Type::Tuple(items) => items.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+impl FromValue for () {
+    fn from_value(_: &Interpreter, _: Value) -> () {
+        ()
+    }
+}
+
+impl IntoValue for () {
+    fn into_value(self, _: &Interpreter, ty: Type) -> Value {
+        Value::new(ValueCore::Tuple(vec![]), ty)
+    }
+}
+
+impl<T: MatchesType> MatchesType for Option<T> {
+    fn matches_type(program: &Program, ty: &Type) -> bool {
+        match get_named_type_args(program, ty, OPTION_MODULE_NAME, OPTION_TYPE_NAME) {
+            Some(args) => T::matches_type(program, &args[0]),
+            None => false,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(interpreter: &Interpreter, value: Value) -> Option<T> {
+        let cache = interpreter.get_typedef_id_cache();
+        let some_index = cache.option_variants.get_index("Some");
+        let none_index = cache.option_variants.get_index("None");
+        value
+            .core
+            .as_option(some_index, none_index)
+            .map(|v| T::from_value(interpreter, v))
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    fn into_value(self, interpreter: &Interpreter, ty: Type) -> Value {
+        let item_ty = ty.get_type_args().remove(0);
+        match self {
+            Some(v) => create_some(interpreter, v.into_value(interpreter, item_ty)),
+            None => create_none(interpreter, item_ty),
+        }
+    }
+}
+
+impl<T: MatchesType, E: MatchesType> MatchesType for Result<T, E> {
+    fn matches_type(program: &Program, ty: &Type) -> bool {
+        match get_named_type_args(program, ty, RESULT_MODULE_NAME, RESULT_TYPE_NAME) {
+            Some(args) => T::matches_type(program, &args[0]) && E::matches_type(program, &args[1]),
+            None => false,
+        }
+    }
+}
+
+impl<T: FromValue, E: FromValue> FromValue for Result<T, E> {
+    fn from_value(interpreter: &Interpreter, value: Value) -> Result<T, E> {
+        let cache = interpreter.get_typedef_id_cache();
+        let ok_index = cache.result_variants.get_index("Ok");
+        match value.core {
+            ValueCore::Variant(_, index, mut items) => {
+                let item = items.remove(0);
+                if index == ok_index {
+                    Ok(T::from_value(interpreter, item))
+                } else {
+                    Err(E::from_value(interpreter, item))
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<T: IntoValue, E: IntoValue> IntoValue for Result<T, E> {
+    fn into_value(self, interpreter: &Interpreter, ty: Type) -> Value {
+        let mut type_args = ty.get_type_args();
+        let err_ty = type_args.remove(1);
+        let ok_ty = type_args.remove(0);
+        match self {
+            Ok(v) => create_ok(interpreter, v.into_value(interpreter, ok_ty), ty),
+            Err(e) => create_err(interpreter, e.into_value(interpreter, err_ty), ty),
+        }
+    }
+}
+
+impl<T: MatchesType> MatchesType for Vec<T> {
+    fn matches_type(program: &Program, ty: &Type) -> bool {
+        match get_named_type_args(program, ty, LIST_MODULE_NAME, LIST_TYPE_NAME) {
+            Some(args) => T::matches_type(program, &args[0]),
+            None => false,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(interpreter: &Interpreter, value: Value) -> Vec<T> {
+        value
+            .core
+            .as_list()
+            .into_iter()
+            .map(|v| T::from_value(interpreter, v))
+            .collect()
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self, interpreter: &Interpreter, ty: Type) -> Value {
+        let item_ty = ty.get_type_args().remove(0);
+        let items: Vector<_> = self
+            .into_iter()
+            .map(|v| v.into_value(interpreter, item_ty.clone()))
+            .collect();
+        Value::new(ValueCore::List(items), ty)
+    }
+}
+
+impl<K: MatchesType, V: MatchesType> MatchesType for BTreeMap<K, V> {
+    fn matches_type(program: &Program, ty: &Type) -> bool {
+        match get_named_type_args(program, ty, MAP_MODULE_NAME, MAP_TYPE_NAME) {
+            Some(args) => K::matches_type(program, &args[0]) && V::matches_type(program, &args[1]),
+            None => false,
+        }
+    }
+}
+
+impl<K: FromValue + Ord, V: FromValue> FromValue for BTreeMap<K, V> {
+    fn from_value(interpreter: &Interpreter, value: Value) -> BTreeMap<K, V> {
+        value
+            .core
+            .as_map()
+            .into_iter()
+            .map(|(k, v)| {
+                (
+                    K::from_value(interpreter, k.value),
+                    V::from_value(interpreter, v),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<K: IntoValue, V: IntoValue> IntoValue for BTreeMap<K, V> {
+    fn into_value(self, interpreter: &Interpreter, ty: Type) -> Value {
+        let mut type_args = ty.get_type_args();
+        let value_ty = type_args.remove(1);
+        let key_ty = type_args.remove(0);
+        let items: OrdMap<_, _> = self
+            .into_iter()
+            .map(|(k, v)| {
+                let key = k.into_value(interpreter, key_ty.clone());
+                let value = v.into_value(interpreter, value_ty.clone());
+                (interpreter.create_key(key), value)
+            })
+            .collect();
+        Value::new(ValueCore::Map(items), ty)
+    }
+}
+
+macro_rules! impl_tuple_conversion {
+    ($count:expr, $($name:ident $index:tt),+) => {
+        impl<$($name: MatchesType),+> MatchesType for ($($name,)+) {
+            fn matches_type(program: &Program, ty: &Type) -> bool {
+                match ty {
+                    Type::Tuple(item_types) => {
+                        item_types.len() == $count $(&& $name::matches_type(program, &item_types[$index]))+
+                    }
+                    _ => false,
+                }
+            }
+        }
+
+        impl<$($name: FromValue),+> FromValue for ($($name,)+) {
+            fn from_value(interpreter: &Interpreter, value: Value) -> ($($name,)+) {
+                let items = value.core.as_tuple();
+                ($($name::from_value(interpreter, items[$index].clone()),)+)
+            }
+        }
+
+        impl<$($name: IntoValue),+> IntoValue for ($($name,)+) {
+            fn into_value(self, interpreter: &Interpreter, ty: Type) -> Value {
+                let item_types = match &ty {
+                    Type::Tuple(item_types) => item_types.clone(),
+                    _ => unreachable!(),
+                };
+                let items = vec![$(self.$index.into_value(interpreter, item_types[$index].clone())),+];
+                Value::new(ValueCore::Tuple(items), ty)
+            }
+        }
+    };
+}
+
+impl_tuple_conversion!(2, A 0, B 1);
+impl_tuple_conversion!(3, A 0, B 1, C 2);
+impl_tuple_conversion!(4, A 0, B 1, C 2, D 3);
+
+/// Converts a tuple of Rust values into the arguments of a function call.
+pub trait IntoArgs {
+    fn get_arg_count() -> usize;
+
+    fn matches_types(program: &Program, arg_types: &[Type]) -> bool;
+
+    fn into_args(self, interpreter: &Interpreter, arg_types: Vec<Type>) -> Vec<Value>;
+}
+
+macro_rules! impl_into_args {
+    ($count:expr $(, $name:ident $index:tt)*) => {
+        impl<$($name: IntoValue),*> IntoArgs for ($($name,)*) {
+            fn get_arg_count() -> usize {
+                $count
+            }
+
+            #[allow(unused_variables)]
+            fn matches_types(program: &Program, arg_types: &[Type]) -> bool {
+                true $(&& $name::matches_type(program, &arg_types[$index]))*
+            }
+
+            #[allow(unused_variables)]
+            fn into_args(self, interpreter: &Interpreter, arg_types: Vec<Type>) -> Vec<Value> {
+                vec![$(self.$index.into_value(interpreter, arg_types[$index].clone())),*]
+            }
+        }
+    };
+}
+
+impl_into_args!(0);
+impl_into_args!(1, A 0);
+impl_into_args!(2, A 0, B 1);
+impl_into_args!(3, A 0, B 1, C 2);
+impl_into_args!(4, A 0, B 1, C 2, D 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use siko_location_info::filepath::FilePath;
+    use siko_location_info::location_info::LocationInfo;
+    use siko_name_resolver::resolver::Resolver;
+    use siko_parser::lexer::Lexer;
+    use siko_parser::parser::Parser;
+    use siko_syntax::program::Program as SyntaxProgram;
+    use siko_type_checker::typechecker::Typechecker;
+    use std::fmt::Debug;
+    use std::fs;
+    use std::path::Path;
+    use std::rc::Rc;
+
+    // Map keys are compared with the instances resolved by the type checker
+    const PROGRAM: &str = "module Test where
+
+import Map
+
+lookup :: Map String (List Float) -> String -> Option (List Float)
+lookup m k = Map.get m k
+";
+
+    fn parse(
+        content: &str,
+        file_path: FilePath,
+        program: &mut SyntaxProgram,
+        location_info: &mut LocationInfo,
+    ) {
+        let mut errors = Vec::new();
+        let tokens = Lexer::new(content, file_path.clone())
+            .process(&mut errors)
+            .unwrap();
+        let mut parser = Parser::new(file_path, &tokens[..], program, location_info);
+        parser.parse().unwrap();
+    }
+
+    fn parse_std(path: &Path, program: &mut SyntaxProgram, location_info: &mut LocationInfo) {
+        for entry in fs::read_dir(path).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                parse_std(&path, program, location_info);
+            } else if let Some(ext) = path.extension() {
+                if ext == "sk" {
+                    let content = fs::read_to_string(&path).unwrap();
+                    let file_path = FilePath::new(format!("{}", path.display()));
+                    parse(&content, file_path, program, location_info);
+                }
+            }
+        }
+    }
+
+    // An interpreter for the std, which has all the types the conversions need
+    fn create_interpreter() -> Rc<Interpreter> {
+        let mut program = SyntaxProgram::new();
+        let mut location_info = LocationInfo::new();
+        let std_path = format!("{}/../../std", env!("CARGO_MANIFEST_DIR"));
+        parse_std(Path::new(&std_path), &mut program, &mut location_info);
+        let file_path = FilePath::new(format!("test.sk"));
+        parse(PROGRAM, file_path, &mut program, &mut location_info);
+        let mut ir_program = Resolver::new().resolve(&program).unwrap();
+        Typechecker::new().check(&mut ir_program, false).unwrap();
+        let registry = Interpreter::create_extern_registry();
+        Interpreter::create(ir_program, 1000, false, Vec::new(), registry)
+    }
+
+    fn get_named_type(program: &Program, module: &str, name: &str, args: Vec<Type>) -> Type {
+        let id = program.get_named_type(module, name);
+        Type::Named(name.to_string(), id, args)
+    }
+
+    fn round_trip<T: FromValue + IntoValue + Clone + PartialEq + Debug>(
+        interpreter: &Interpreter,
+        value: T,
+        ty: Type,
+    ) {
+        assert!(T::matches_type(interpreter.get_program(), &ty));
+        let converted = value.clone().into_value(interpreter, ty);
+        assert_eq!(T::from_value(interpreter, converted), value);
+    }
+
+    #[test]
+    fn tuples() {
+        let interpreter = create_interpreter();
+        let program = interpreter.get_program();
+        let int_ty = program.get_int_type();
+        let string_ty = program.get_string_type();
+        let pair_ty = Type::Tuple(vec![int_ty.clone(), string_ty.clone()]);
+        round_trip(&interpreter, (1i64, format!("a")), pair_ty.clone());
+        let triple_ty = Type::Tuple(vec![int_ty.clone(), program.get_bool_type(), pair_ty]);
+        round_trip(&interpreter, (2i64, true, (3i64, format!("b"))), triple_ty);
+        let wrong_ty = Type::Tuple(vec![string_ty, int_ty]);
+        assert!(!<(i64, String)>::matches_type(program, &wrong_ty));
+    }
+
+    #[test]
+    fn options() {
+        let interpreter = create_interpreter();
+        let program = interpreter.get_program();
+        let option_ty = program.get_option_type(program.get_int_type());
+        round_trip(&interpreter, Some(1i64), option_ty.clone());
+        round_trip(&interpreter, None::<i64>, option_ty.clone());
+        let nested_ty = program.get_option_type(option_ty);
+        round_trip(&interpreter, Some(None::<i64>), nested_ty);
+    }
+
+    #[test]
+    fn results() {
+        let interpreter = create_interpreter();
+        let program = interpreter.get_program();
+        let args = vec![program.get_int_type(), program.get_string_type()];
+        let result_ty = get_named_type(program, RESULT_MODULE_NAME, RESULT_TYPE_NAME, args);
+        round_trip::<Result<i64, String>>(&interpreter, Ok(1), result_ty.clone());
+        round_trip::<Result<i64, String>>(&interpreter, Err(format!("e")), result_ty.clone());
+        assert!(!<Result<String, i64>>::matches_type(program, &result_ty));
+    }
+
+    #[test]
+    fn lists() {
+        let interpreter = create_interpreter();
+        let program = interpreter.get_program();
+        let list_ty = program.get_list_type(program.get_char_type());
+        round_trip(&interpreter, vec!['a', 'b'], list_ty.clone());
+        round_trip(&interpreter, Vec::<char>::new(), list_ty.clone());
+        let nested_ty = program.get_list_type(list_ty);
+        round_trip(&interpreter, vec![vec!['a'], vec![]], nested_ty);
+        assert!(!Vec::<i64>::matches_type(program, &program.get_int_type()));
+    }
+
+    #[test]
+    fn maps() {
+        let interpreter = create_interpreter();
+        let program = interpreter.get_program();
+        let float_list_ty = program.get_list_type(program.get_float_type());
+        let args = vec![program.get_string_type(), float_list_ty];
+        let map_ty = get_named_type(program, MAP_MODULE_NAME, MAP_TYPE_NAME, args);
+        let mut map = BTreeMap::new();
+        map.insert(format!("b"), vec![2.5]);
+        map.insert(format!("a"), vec![1.0, 1.5]);
+        round_trip(&interpreter, map, map_ty.clone());
+        round_trip(
+            &interpreter,
+            BTreeMap::<String, Vec<f64>>::new(),
+            map_ty.clone(),
+        );
+        assert!(!BTreeMap::<String, Vec<i64>>::matches_type(
+            program, &map_ty
+        ));
+    }
+}
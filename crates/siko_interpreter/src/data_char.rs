@@ -0,0 +1,143 @@
+use crate::environment::Environment;
+use crate::error::RuntimeError;
+use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
+use crate::interpreter::Interpreter;
+use crate::util::get_opt_ordering_value;
+use crate::util::get_ordering_value;
+use crate::value::Value;
+use crate::value::ValueCore;
+use siko_constants::CHAR_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+use std::char;
+
+pub struct CharPartialEq {}
+
+impl ExternFunction for CharPartialEq {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l = environment.get_arg_by_index(0).core.as_char();
+        let r = environment.get_arg_by_index(1).core.as_char();
+        return Ok(interpreter.get_bool_value(l == r));
+    }
+}
+
+pub struct CharPartialOrd {}
+
+impl ExternFunction for CharPartialOrd {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l = environment.get_arg_by_index(0).core.as_char();
+        let r = environment.get_arg_by_index(1).core.as_char();
+        let ord = l.partial_cmp(&r);
+        return Ok(get_opt_ordering_value(interpreter, ord));
+    }
+}
+
+pub struct CharOrd {}
+
+impl ExternFunction for CharOrd {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l = environment.get_arg_by_index(0).core.as_char();
+        let r = environment.get_arg_by_index(1).core.as_char();
+        let ord = l.cmp(&r);
+        return Ok(get_ordering_value(interpreter, ord));
+    }
+}
+
+pub struct CharShow {}
+
+impl ExternFunction for CharShow {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let value = environment.get_arg_by_index(0).core.as_char();
+        return Ok(Value::new(ValueCore::String(format!("{:?}", value)), ty));
+    }
+}
+
+pub struct CharToInt {}
+
+impl ExternFunction for CharToInt {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let value = environment.get_arg_by_index(0).core.as_char();
+        return Ok(Value::new(ValueCore::Int(value as i64), ty));
+    }
+}
+
+pub struct CharFromInt {}
+
+impl ExternFunction for CharFromInt {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let value = environment.get_arg_by_index(0).core.as_int();
+        let c = if value < 0 || value > u32::max_value() as i64 {
+            None
+        } else {
+            char::from_u32(value as u32)
+        };
+        match c {
+            Some(c) => return Ok(Value::new(ValueCore::Char(c), ty)),
+            None => {
+                return Err(interpreter
+                    .runtime_error(format!("Invalid character code {}", value), current_expr));
+            }
+        }
+    }
+}
+
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(CHAR_MODULE_NAME, "opEq", Box::new(CharPartialEq {}));
+    registry.add_extern_function(CHAR_MODULE_NAME, "partialCmp", Box::new(CharPartialOrd {}));
+    registry.add_extern_function(CHAR_MODULE_NAME, "cmp", Box::new(CharOrd {}));
+    registry.add_extern_function(CHAR_MODULE_NAME, "show", Box::new(CharShow {}));
+    registry.add_extern_function(CHAR_MODULE_NAME, "toInt", Box::new(CharToInt {}));
+    registry.add_extern_function(CHAR_MODULE_NAME, "fromInt", Box::new(CharFromInt {}));
+    registry.add_native_function(CHAR_MODULE_NAME, "toString", |c: char| c.to_string());
+    registry.add_native_function(CHAR_MODULE_NAME, "fromString", |s: String| {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    });
+}
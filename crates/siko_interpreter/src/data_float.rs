@@ -1,5 +1,7 @@
 use crate::environment::Environment;
+use crate::error::RuntimeError;
 use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
 use crate::interpreter::Interpreter;
 use crate::util::get_opt_ordering_value;
 use crate::value::Value;
@@ -14,14 +16,15 @@ pub struct FloatAdd {}
 impl ExternFunction for FloatAdd {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_float();
         let r = environment.get_arg_by_index(1).core.as_float();
-        return Value::new(ValueCore::Float(l + r), ty);
+        return Ok(Value::new(ValueCore::Float(l + r), ty));
     }
 }
 
@@ -30,14 +33,15 @@ pub struct FloatSub {}
 impl ExternFunction for FloatSub {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_float();
         let r = environment.get_arg_by_index(1).core.as_float();
-        return Value::new(ValueCore::Float(l - r), ty);
+        return Ok(Value::new(ValueCore::Float(l - r), ty));
     }
 }
 
@@ -46,14 +50,15 @@ pub struct FloatMul {}
 impl ExternFunction for FloatMul {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_float();
         let r = environment.get_arg_by_index(1).core.as_float();
-        return Value::new(ValueCore::Float(l * r), ty);
+        return Ok(Value::new(ValueCore::Float(l * r), ty));
     }
 }
 
@@ -62,14 +67,48 @@ pub struct FloatDiv {}
 impl ExternFunction for FloatDiv {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_float();
         let r = environment.get_arg_by_index(1).core.as_float();
-        return Value::new(ValueCore::Float(l / r), ty);
+        return Ok(Value::new(ValueCore::Float(l / r), ty));
+    }
+}
+
+pub struct FloatRem {}
+
+impl ExternFunction for FloatRem {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l = environment.get_arg_by_index(0).core.as_float();
+        let r = environment.get_arg_by_index(1).core.as_float();
+        return Ok(Value::new(ValueCore::Float(l % r), ty));
+    }
+}
+
+pub struct FloatNeg {}
+
+impl ExternFunction for FloatNeg {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Ok(Value::new(ValueCore::Float(-value), ty));
     }
 }
 
@@ -78,14 +117,15 @@ pub struct FloatPartialEq {}
 impl ExternFunction for FloatPartialEq {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_float();
         let r = environment.get_arg_by_index(1).core.as_float();
-        return Interpreter::get_bool_value(l == r);
+        return Ok(interpreter.get_bool_value(l == r));
     }
 }
 
@@ -94,15 +134,16 @@ pub struct FloatPartialOrd {}
 impl ExternFunction for FloatPartialOrd {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_float();
         let r = environment.get_arg_by_index(1).core.as_float();
         let ord = l.partial_cmp(&r);
-        return get_opt_ordering_value(ord);
+        return Ok(get_opt_ordering_value(interpreter, ord));
     }
 }
 
@@ -111,22 +152,35 @@ pub struct FloatShow {}
 impl ExternFunction for FloatShow {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let value = environment.get_arg_by_index(0).core.as_float();
-        return Value::new(ValueCore::String(value.to_string()), ty);
+        return Ok(Value::new(ValueCore::String(value.to_string()), ty));
     }
 }
 
-pub fn register_extern_functions(interpreter: &mut Interpreter) {
-    interpreter.add_extern_function(FLOAT_MODULE_NAME, "opAdd", Box::new(FloatAdd {}));
-    interpreter.add_extern_function(FLOAT_MODULE_NAME, "opSub", Box::new(FloatSub {}));
-    interpreter.add_extern_function(FLOAT_MODULE_NAME, "opMul", Box::new(FloatMul {}));
-    interpreter.add_extern_function(FLOAT_MODULE_NAME, "opDiv", Box::new(FloatDiv {}));
-    interpreter.add_extern_function(FLOAT_MODULE_NAME, "opEq", Box::new(FloatPartialEq {}));
-    interpreter.add_extern_function(FLOAT_MODULE_NAME, "partialCmp", Box::new(FloatPartialOrd {}));
-    interpreter.add_extern_function(FLOAT_MODULE_NAME, "show", Box::new(FloatShow {}));
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(FLOAT_MODULE_NAME, "opAdd", Box::new(FloatAdd {}));
+    registry.add_extern_function(FLOAT_MODULE_NAME, "opSub", Box::new(FloatSub {}));
+    registry.add_extern_function(FLOAT_MODULE_NAME, "opMul", Box::new(FloatMul {}));
+    registry.add_extern_function(FLOAT_MODULE_NAME, "opDiv", Box::new(FloatDiv {}));
+    registry.add_extern_function(FLOAT_MODULE_NAME, "opEq", Box::new(FloatPartialEq {}));
+    registry.add_extern_function(FLOAT_MODULE_NAME, "partialCmp", Box::new(FloatPartialOrd {}));
+    registry.add_extern_function(FLOAT_MODULE_NAME, "show", Box::new(FloatShow {}));
+    registry.add_extern_function(FLOAT_MODULE_NAME, "opRem", Box::new(FloatRem {}));
+    registry.add_extern_function(FLOAT_MODULE_NAME, "opNeg", Box::new(FloatNeg {}));
+    registry.add_native_function(FLOAT_MODULE_NAME, "sqrt", |v: f64| v.sqrt());
+    registry.add_native_function(FLOAT_MODULE_NAME, "floor", |v: f64| v.floor());
+    registry.add_native_function(FLOAT_MODULE_NAME, "ceil", |v: f64| v.ceil());
+    registry.add_native_function(FLOAT_MODULE_NAME, "round", |v: f64| v.round());
+    registry.add_native_function(FLOAT_MODULE_NAME, "sin", |v: f64| v.sin());
+    registry.add_native_function(FLOAT_MODULE_NAME, "cos", |v: f64| v.cos());
+    registry.add_native_function(FLOAT_MODULE_NAME, "exp", |v: f64| v.exp());
+    registry.add_native_function(FLOAT_MODULE_NAME, "ln", |v: f64| v.ln());
+    registry.add_native_function(FLOAT_MODULE_NAME, "isNaN", |v: f64| v.is_nan());
+    registry.add_native_function(FLOAT_MODULE_NAME, "truncate", |v: f64| v as i64);
 }
@@ -1,5 +1,7 @@
 use crate::environment::Environment;
+use crate::error::RuntimeError;
 use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
 use crate::interpreter::Interpreter;
 use crate::util::get_opt_ordering_value;
 use crate::util::get_ordering_value;
@@ -10,20 +12,65 @@ use siko_ir::function::NamedFunctionKind;
 use siko_ir::types::Type;
 use siko_constants::INT_MODULE_NAME;
 
+// The first item of the pair is the wrapped result, the second one tells whether an overflow happened
+fn create_int_result(
+    interpreter: &Interpreter,
+    result: (i64, bool),
+    current_expr: Option<ExprId>,
+    ty: Type,
+) -> Result<Value, RuntimeError> {
+    let (value, overflow) = result;
+    if overflow && !interpreter.is_wrapping_arithmetic() {
+        return Err(interpreter.runtime_error(format!("Integer overflow"), current_expr));
+    }
+    return Ok(Value::new(ValueCore::Int(value), ty));
+}
+
+fn check_shift_amount(amount: i64) -> Result<i64, RuntimeError> {
+    if amount < 0 || amount >= 64 {
+        return Err(RuntimeError::new(format!(
+            "Invalid shift amount {}",
+            amount
+        )));
+    }
+    return Ok(amount);
+}
+
+fn overflowing_pow(base: i64, exponent: i64) -> (i64, bool) {
+    let mut result: i64 = 1;
+    let mut base = base;
+    let mut exponent = exponent;
+    let mut overflow = false;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            let (value, o) = result.overflowing_mul(base);
+            result = value;
+            overflow = overflow || o;
+        }
+        exponent = exponent >> 1;
+        if exponent > 0 {
+            let (value, o) = base.overflowing_mul(base);
+            base = value;
+            overflow = overflow || o;
+        }
+    }
+    (result, overflow)
+}
 
 pub struct IntAdd {}
 
 impl ExternFunction for IntAdd {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
-        _: Option<ExprId>,
+        current_expr: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_int();
         let r = environment.get_arg_by_index(1).core.as_int();
-        return Value::new(ValueCore::Int(l + r), ty);
+        return create_int_result(interpreter, l.overflowing_add(r), current_expr, ty);
     }
 }
 
@@ -32,14 +79,15 @@ pub struct IntSub {}
 impl ExternFunction for IntSub {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
-        _: Option<ExprId>,
+        current_expr: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_int();
         let r = environment.get_arg_by_index(1).core.as_int();
-        return Value::new(ValueCore::Int(l - r), ty);
+        return create_int_result(interpreter, l.overflowing_sub(r), current_expr, ty);
     }
 }
 
@@ -48,14 +96,15 @@ pub struct IntMul {}
 impl ExternFunction for IntMul {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
-        _: Option<ExprId>,
+        current_expr: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_int();
         let r = environment.get_arg_by_index(1).core.as_int();
-        return Value::new(ValueCore::Int(l * r), ty);
+        return create_int_result(interpreter, l.overflowing_mul(r), current_expr, ty);
     }
 }
 
@@ -64,14 +113,90 @@ pub struct IntDiv {}
 impl ExternFunction for IntDiv {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
-        _: Option<ExprId>,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        if r == 0 {
+            return Err(interpreter.runtime_error(format!("Division by zero"), current_expr));
+        }
+        return create_int_result(interpreter, l.overflowing_div(r), current_expr, ty);
+    }
+}
+
+pub struct IntRem {}
+
+impl ExternFunction for IntRem {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        if r == 0 {
+            return Err(interpreter.runtime_error(format!("Division by zero"), current_expr));
+        }
+        return create_int_result(interpreter, l.overflowing_rem(r), current_expr, ty);
+    }
+}
+
+pub struct IntNeg {}
+
+impl ExternFunction for IntNeg {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let value = environment.get_arg_by_index(0).core.as_int();
+        return create_int_result(interpreter, value.overflowing_neg(), current_expr, ty);
+    }
+}
+
+pub struct IntAbs {}
+
+impl ExternFunction for IntAbs {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let value = environment.get_arg_by_index(0).core.as_int();
+        return create_int_result(interpreter, value.overflowing_abs(), current_expr, ty);
+    }
+}
+
+pub struct IntPow {}
+
+impl ExternFunction for IntPow {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_int();
         let r = environment.get_arg_by_index(1).core.as_int();
-        return Value::new(ValueCore::Int(l / r), ty);
+        if r < 0 {
+            return Err(interpreter.runtime_error(format!("Negative exponent {}", r), current_expr));
+        }
+        return create_int_result(interpreter, overflowing_pow(l, r), current_expr, ty);
     }
 }
 
@@ -80,14 +205,15 @@ pub struct IntPartialEq {}
 impl ExternFunction for IntPartialEq {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_int();
         let r = environment.get_arg_by_index(1).core.as_int();
-        return Interpreter::get_bool_value(l == r);
+        return Ok(interpreter.get_bool_value(l == r));
     }
 }
 
@@ -96,15 +222,16 @@ pub struct IntPartialOrd {}
 impl ExternFunction for IntPartialOrd {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_int();
         let r = environment.get_arg_by_index(1).core.as_int();
         let ord = l.partial_cmp(&r);
-        return get_opt_ordering_value(ord);
+        return Ok(get_opt_ordering_value(interpreter, ord));
     }
 }
 
@@ -113,15 +240,16 @@ pub struct IntOrd {}
 impl ExternFunction for IntOrd {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_int();
         let r = environment.get_arg_by_index(1).core.as_int();
         let ord = l.cmp(&r);
-        return get_ordering_value(ord);
+        return Ok(get_ordering_value(interpreter, ord));
     }
 }
 
@@ -130,23 +258,63 @@ pub struct IntShow {}
 impl ExternFunction for IntShow {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let value = environment.get_arg_by_index(0).core.as_int();
-        return Value::new(ValueCore::String(value.to_string()), ty);
+        return Ok(Value::new(ValueCore::String(value.to_string()), ty));
     }
 }
 
-pub fn register_extern_functions(interpreter: &mut Interpreter) {
-    interpreter.add_extern_function(INT_MODULE_NAME, "opAdd", Box::new(IntAdd {}));
-    interpreter.add_extern_function(INT_MODULE_NAME, "opSub", Box::new(IntSub {}));
-    interpreter.add_extern_function(INT_MODULE_NAME, "opMul", Box::new(IntMul {}));
-    interpreter.add_extern_function(INT_MODULE_NAME, "opDiv", Box::new(IntDiv {}));
-    interpreter.add_extern_function(INT_MODULE_NAME, "opEq", Box::new(IntPartialEq {}));
-    interpreter.add_extern_function(INT_MODULE_NAME, "partialCmp", Box::new(IntPartialOrd {}));
-    interpreter.add_extern_function(INT_MODULE_NAME, "cmp", Box::new(IntOrd {}));
-    interpreter.add_extern_function(INT_MODULE_NAME, "show", Box::new(IntShow {}));
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(INT_MODULE_NAME, "opAdd", Box::new(IntAdd {}));
+    registry.add_extern_function(INT_MODULE_NAME, "opSub", Box::new(IntSub {}));
+    registry.add_extern_function(INT_MODULE_NAME, "opMul", Box::new(IntMul {}));
+    registry.add_extern_function(INT_MODULE_NAME, "opDiv", Box::new(IntDiv {}));
+    registry.add_extern_function(INT_MODULE_NAME, "opEq", Box::new(IntPartialEq {}));
+    registry.add_extern_function(INT_MODULE_NAME, "partialCmp", Box::new(IntPartialOrd {}));
+    registry.add_extern_function(INT_MODULE_NAME, "cmp", Box::new(IntOrd {}));
+    registry.add_extern_function(INT_MODULE_NAME, "show", Box::new(IntShow {}));
+    registry.add_extern_function(INT_MODULE_NAME, "opRem", Box::new(IntRem {}));
+    registry.add_extern_function(INT_MODULE_NAME, "opNeg", Box::new(IntNeg {}));
+    registry.add_extern_function(INT_MODULE_NAME, "abs", Box::new(IntAbs {}));
+    registry.add_extern_function(INT_MODULE_NAME, "pow", Box::new(IntPow {}));
+    registry.add_native_function(INT_MODULE_NAME, "checkedAdd", |l: i64, r: i64| {
+        l.checked_add(r)
+    });
+    registry.add_native_function(INT_MODULE_NAME, "checkedSub", |l: i64, r: i64| {
+        l.checked_sub(r)
+    });
+    registry.add_native_function(INT_MODULE_NAME, "checkedMul", |l: i64, r: i64| {
+        l.checked_mul(r)
+    });
+    registry.add_native_function(INT_MODULE_NAME, "checkedDiv", |l: i64, r: i64| {
+        l.checked_div(r)
+    });
+    registry.add_native_function(INT_MODULE_NAME, "checkedRem", |l: i64, r: i64| {
+        l.checked_rem(r)
+    });
+    registry.add_native_function(INT_MODULE_NAME, "checkedPow", |l: i64, r: i64| {
+        if r < 0 {
+            return None;
+        }
+        match overflowing_pow(l, r) {
+            (_, true) => None,
+            (value, false) => Some(value),
+        }
+    });
+    registry.add_native_function(INT_MODULE_NAME, "bitAnd", |l: i64, r: i64| l & r);
+    registry.add_native_function(INT_MODULE_NAME, "bitOr", |l: i64, r: i64| l | r);
+    registry.add_native_function(INT_MODULE_NAME, "bitXor", |l: i64, r: i64| l ^ r);
+    registry.add_native_function(INT_MODULE_NAME, "bitNot", |v: i64| !v);
+    registry.add_native_function(INT_MODULE_NAME, "shiftLeft", |l: i64, r: i64| {
+        check_shift_amount(r).map(|r| l << r)
+    });
+    registry.add_native_function(INT_MODULE_NAME, "shiftRight", |l: i64, r: i64| {
+        check_shift_amount(r).map(|r| l >> r)
+    });
+    registry.add_native_function(INT_MODULE_NAME, "toFloat", |v: i64| v as f64);
 }
@@ -1,33 +1,453 @@
 use crate::environment::Environment;
+use crate::error::RuntimeError;
 use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
 use crate::interpreter::Interpreter;
+use crate::util::create_none;
+use crate::util::create_some;
+use crate::util::get_opt_ordering_value;
+use crate::util::get_ordering_value;
 use crate::value::Value;
 use crate::value::ValueCore;
+use im_rc::Vector;
+use siko_constants::LIST_MODULE_NAME;
 use siko_ir::expr::ExprId;
 use siko_ir::function::NamedFunctionKind;
 use siko_ir::types::Type;
-use siko_constants::LIST_MODULE_NAME;
+use std::cmp::Ordering;
 
 pub struct Show {}
 
 impl ExternFunction for Show {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let list = environment.get_arg_by_index(0).core.as_list();
         let mut subs = Vec::new();
         for item in list {
-            let s = Interpreter::call_show(item);
+            let s = interpreter.call_show(item)?;
             subs.push(s);
         }
-        return Value::new(ValueCore::String(format!("[{}]", subs.join(", "))), ty);
+        return Ok(Value::new(
+            ValueCore::String(format!("[{}]", subs.join(", "))),
+            ty,
+        ));
+    }
+}
+
+pub struct ListPartialEq {}
+
+impl ExternFunction for ListPartialEq {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l1 = environment.get_arg_by_index(0).core.as_list();
+        let l2 = environment.get_arg_by_index(1).core.as_list();
+        if l1.len() != l2.len() {
+            return Ok(interpreter.get_bool_value(false));
+        }
+        for (item1, item2) in l1.into_iter().zip(l2.into_iter()) {
+            let value = interpreter.call_op_partial_eq(item1, item2)?;
+            if !value.core.as_bool() {
+                return Ok(interpreter.get_bool_value(false));
+            }
+        }
+        return Ok(interpreter.get_bool_value(true));
+    }
+}
+
+pub struct ListPartialOrd {}
+
+impl ExternFunction for ListPartialOrd {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l1 = environment.get_arg_by_index(0).core.as_list();
+        let l2 = environment.get_arg_by_index(1).core.as_list();
+        let len_ordering = l1.len().cmp(&l2.len());
+        for (item1, item2) in l1.into_iter().zip(l2.into_iter()) {
+            let value = interpreter.call_op_partial_cmp(item1, item2)?;
+            if let Some(ordering) = value.core.as_option(0, 1) {
+                if ordering.core.as_ordering(0, 1, 2) == Ordering::Equal {
+                    continue;
+                }
+            }
+            return Ok(value);
+        }
+        return Ok(get_opt_ordering_value(interpreter, Some(len_ordering)));
+    }
+}
+
+pub struct ListOrd {}
+
+impl ExternFunction for ListOrd {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l1 = environment.get_arg_by_index(0).core.as_list();
+        let l2 = environment.get_arg_by_index(1).core.as_list();
+        let len_ordering = l1.len().cmp(&l2.len());
+        for (item1, item2) in l1.into_iter().zip(l2.into_iter()) {
+            let value = interpreter.call_op_cmp(item1, item2)?;
+            if value.core.as_ordering(0, 1, 2) != Ordering::Equal {
+                return Ok(value);
+            }
+        }
+        return Ok(get_ordering_value(interpreter, len_ordering));
+    }
+}
+
+pub struct Length {}
+
+impl ExternFunction for Length {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let list = environment.get_arg_by_index(0).core.as_list();
+        return Ok(Value::new(ValueCore::Int(list.len() as i64), ty));
+    }
+}
+
+pub struct Head {}
+
+impl ExternFunction for Head {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let arg = environment.get_arg_by_index(0);
+        let mut list_type_args = arg.ty.get_type_args();
+        let list = arg.core.as_list();
+        match list.into_iter().next() {
+            Some(item) => return Ok(create_some(interpreter, item)),
+            None => return Ok(create_none(interpreter, list_type_args.remove(0))),
+        }
+    }
+}
+
+pub struct Tail {}
+
+impl ExternFunction for Tail {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let mut arg = environment.get_arg_by_index(0);
+        let mut list = arg.core.as_list();
+        if list.is_empty() {
+            return Ok(create_none(interpreter, arg.ty));
+        }
+        list.pop_front();
+        arg.core = ValueCore::List(list);
+        return Ok(create_some(interpreter, arg));
+    }
+}
+
+pub struct Cons {}
+
+impl ExternFunction for Cons {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let item = environment.get_arg_by_index(0);
+        let mut list = environment.get_arg_by_index(1).core.as_list();
+        list.push_front(item);
+        return Ok(Value::new(ValueCore::List(list), ty));
+    }
+}
+
+pub struct Append {}
+
+impl ExternFunction for Append {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let mut list = environment.get_arg_by_index(0).core.as_list();
+        let other = environment.get_arg_by_index(1).core.as_list();
+        list.append(other);
+        return Ok(Value::new(ValueCore::List(list), ty));
+    }
+}
+
+pub struct Map {}
+
+impl ExternFunction for Map {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let f = environment.get_arg_by_index(0);
+        let list = environment.get_arg_by_index(1).core.as_list();
+        let mut result = Vector::new();
+        for item in list {
+            let value = interpreter.call_function_value(f.clone(), vec![item], current_expr)?;
+            result.push_back(value);
+        }
+        return Ok(Value::new(ValueCore::List(result), ty));
+    }
+}
+
+pub struct Filter {}
+
+impl ExternFunction for Filter {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let f = environment.get_arg_by_index(0);
+        let list = environment.get_arg_by_index(1).core.as_list();
+        let mut result = Vector::new();
+        for item in list {
+            let value =
+                interpreter.call_function_value(f.clone(), vec![item.clone()], current_expr)?;
+            if value.core.as_bool() {
+                result.push_back(item);
+            }
+        }
+        return Ok(Value::new(ValueCore::List(result), ty));
+    }
+}
+
+pub struct FoldL {}
+
+impl ExternFunction for FoldL {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let f = environment.get_arg_by_index(0);
+        let mut acc = environment.get_arg_by_index(1);
+        let list = environment.get_arg_by_index(2).core.as_list();
+        for item in list {
+            acc = interpreter.call_function_value(f.clone(), vec![acc, item], current_expr)?;
+        }
+        return Ok(acc);
+    }
+}
+
+pub struct FoldR {}
+
+impl ExternFunction for FoldR {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let f = environment.get_arg_by_index(0);
+        let mut acc = environment.get_arg_by_index(1);
+        let list = environment.get_arg_by_index(2).core.as_list();
+        for item in list.into_iter().rev() {
+            acc = interpreter.call_function_value(f.clone(), vec![item, acc], current_expr)?;
+        }
+        return Ok(acc);
+    }
+}
+
+pub struct Reverse {}
+
+impl ExternFunction for Reverse {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let list = environment.get_arg_by_index(0).core.as_list();
+        let list = list.into_iter().rev().collect();
+        return Ok(Value::new(ValueCore::List(list), ty));
+    }
+}
+
+pub struct Zip {}
+
+impl ExternFunction for Zip {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l1 = environment.get_arg_by_index(0).core.as_list();
+        let l2 = environment.get_arg_by_index(1).core.as_list();
+        let tuple_ty = ty.get_type_args().remove(0);
+        let result = l1
+            .into_iter()
+            .zip(l2.into_iter())
+            .map(|(item1, item2)| {
+                Value::new(ValueCore::Tuple(vec![item1, item2]), tuple_ty.clone())
+            })
+            .collect();
+        return Ok(Value::new(ValueCore::List(result), ty));
+    }
+}
+
+pub struct Nth {}
+
+impl ExternFunction for Nth {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let arg = environment.get_arg_by_index(0);
+        let mut list_type_args = arg.ty.get_type_args();
+        let list = arg.core.as_list();
+        let index = environment.get_arg_by_index(1).core.as_int();
+        if index < 0 || index as usize >= list.len() {
+            return Ok(create_none(interpreter, list_type_args.remove(0)));
+        }
+        return Ok(create_some(interpreter, list[index as usize].clone()));
+    }
+}
+
+pub struct Sort {}
+
+impl ExternFunction for Sort {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let list = environment.get_arg_by_index(0).core.as_list();
+        let mut keys: Vector<_> = list
+            .into_iter()
+            .map(|item| interpreter.create_key(item))
+            .collect();
+        keys.sort();
+        let list = keys.into_iter().map(|key| key.value).collect();
+        return Ok(Value::new(ValueCore::List(list), ty));
+    }
+}
+
+pub struct Contains {}
+
+impl ExternFunction for Contains {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let list = environment.get_arg_by_index(0).core.as_list();
+        let item = environment.get_arg_by_index(1);
+        for i in list {
+            let value = interpreter.call_op_partial_eq(i, item.clone())?;
+            if value.core.as_bool() {
+                return Ok(interpreter.get_bool_value(true));
+            }
+        }
+        return Ok(interpreter.get_bool_value(false));
+    }
+}
+
+pub struct Concat {}
+
+impl ExternFunction for Concat {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let lists = environment.get_arg_by_index(0).core.as_list();
+        let mut result = Vector::new();
+        for list in lists {
+            result.append(list.core.as_list());
+        }
+        return Ok(Value::new(ValueCore::List(result), ty));
     }
 }
 
-pub fn register_extern_functions(interpreter: &mut Interpreter) {
-    interpreter.add_extern_function(LIST_MODULE_NAME, "show", Box::new(Show {}));
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(LIST_MODULE_NAME, "show", Box::new(Show {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "opEq", Box::new(ListPartialEq {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "partialCmp", Box::new(ListPartialOrd {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "cmp", Box::new(ListOrd {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "length", Box::new(Length {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "head", Box::new(Head {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "tail", Box::new(Tail {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "cons", Box::new(Cons {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "append", Box::new(Append {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "map", Box::new(Map {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "filter", Box::new(Filter {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "foldl", Box::new(FoldL {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "foldr", Box::new(FoldR {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "reverse", Box::new(Reverse {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "zip", Box::new(Zip {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "nth", Box::new(Nth {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "sort", Box::new(Sort {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "contains", Box::new(Contains {}));
+    registry.add_extern_function(LIST_MODULE_NAME, "concat", Box::new(Concat {}));
 }
@@ -1,14 +1,16 @@
 use crate::environment::Environment;
+use crate::error::RuntimeError;
 use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
 use crate::interpreter::Interpreter;
 use crate::util::create_none;
 use crate::util::create_some;
 use crate::value::Value;
 use crate::value::ValueCore;
+use im_rc::OrdMap;
 use siko_ir::expr::ExprId;
 use siko_ir::function::NamedFunctionKind;
 use siko_ir::types::Type;
-use std::collections::BTreeMap;
 use siko_constants::MAP_MODULE_NAME;
 
 pub struct Empty {}
@@ -16,12 +18,13 @@ pub struct Empty {}
 impl ExternFunction for Empty {
     fn call(
         &self,
+        _: &Interpreter,
         _: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
-        return Value::new(ValueCore::Map(BTreeMap::new()), ty);
+    ) -> Result<Value, RuntimeError> {
+        return Ok(Value::new(ValueCore::Map(OrdMap::new()), ty));
     }
 }
 
@@ -30,24 +33,25 @@ pub struct Insert {}
 impl ExternFunction for Insert {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let mut first_arg = environment.get_arg_by_index(0);
         let mut map_type_args = first_arg.ty.get_type_args();
         let mut map = first_arg.core.as_map();
-        let key = environment.get_arg_by_index(1);
+        let key = interpreter.create_key(environment.get_arg_by_index(1));
         let value = environment.get_arg_by_index(2);
         let res = map.insert(key, value);
         let v = match res {
-            Some(v) => create_some(v),
-            None => create_none(map_type_args.remove(1)),
+            Some(v) => create_some(interpreter, v),
+            None => create_none(interpreter, map_type_args.remove(1)),
         };
         first_arg.core = ValueCore::Map(map);
         let tuple = Value::new(ValueCore::Tuple(vec![first_arg, v]), ty);
-        return tuple;
+        return Ok(tuple);
     }
 }
 
@@ -56,23 +60,24 @@ pub struct Remove {}
 impl ExternFunction for Remove {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let mut first_arg = environment.get_arg_by_index(0);
         let mut map_type_args = first_arg.ty.get_type_args();
         let mut map = first_arg.core.as_map();
-        let key = environment.get_arg_by_index(1);
+        let key = interpreter.create_key(environment.get_arg_by_index(1));
         let res = map.remove(&key);
         let v = match res {
-            Some(v) => create_some(v),
-            None => create_none(map_type_args.remove(1)),
+            Some(v) => create_some(interpreter, v),
+            None => create_none(interpreter, map_type_args.remove(1)),
         };
         first_arg.core = ValueCore::Map(map);
         let tuple = Value::new(ValueCore::Tuple(vec![first_arg, v]), ty);
-        return tuple;
+        return Ok(tuple);
     }
 }
 
@@ -81,27 +86,353 @@ pub struct Get {}
 impl ExternFunction for Get {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let first_arg = environment.get_arg_by_index(0);
         let mut map_type_args = first_arg.ty.get_type_args();
         let map = first_arg.core.as_map();
-        let key = environment.get_arg_by_index(1);
+        let key = interpreter.create_key(environment.get_arg_by_index(1));
         let res = map.get(&key);
         let v = match res {
-            Some(v) => create_some(v.clone()),
-            None => create_none(map_type_args.remove(1)),
+            Some(v) => create_some(interpreter, v.clone()),
+            None => create_none(interpreter, map_type_args.remove(1)),
         };
-        return v;
+        return Ok(v);
     }
 }
 
-pub fn register_extern_functions(interpreter: &mut Interpreter) {
-    interpreter.add_extern_function(MAP_MODULE_NAME, "empty", Box::new(Empty {}));
-    interpreter.add_extern_function(MAP_MODULE_NAME, "insert", Box::new(Insert {}));
-    interpreter.add_extern_function(MAP_MODULE_NAME, "remove", Box::new(Remove {}));
-    interpreter.add_extern_function(MAP_MODULE_NAME, "get", Box::new(Get {}));
+pub struct Show {}
+
+impl ExternFunction for Show {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        let mut subs = Vec::new();
+        for (key, value) in map {
+            let key = interpreter.call_show(key.value)?;
+            let value = interpreter.call_show(value)?;
+            subs.push(format!("{}: {}", key, value));
+        }
+        return Ok(Value::new(
+            ValueCore::String(format!("{{{}}}", subs.join(", "))),
+            ty,
+        ));
+    }
+}
+
+pub struct MapPartialEq {}
+
+impl ExternFunction for MapPartialEq {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let m1 = environment.get_arg_by_index(0).core.as_map();
+        let m2 = environment.get_arg_by_index(1).core.as_map();
+        if m1.len() != m2.len() {
+            return Ok(interpreter.get_bool_value(false));
+        }
+        for ((key1, value1), (key2, value2)) in m1.into_iter().zip(m2.into_iter()) {
+            let keys_equal = interpreter.call_op_partial_eq(key1.value, key2.value)?;
+            if !keys_equal.core.as_bool() {
+                return Ok(interpreter.get_bool_value(false));
+            }
+            let values_equal = interpreter.call_op_partial_eq(value1, value2)?;
+            if !values_equal.core.as_bool() {
+                return Ok(interpreter.get_bool_value(false));
+            }
+        }
+        return Ok(interpreter.get_bool_value(true));
+    }
+}
+
+pub struct Size {}
+
+impl ExternFunction for Size {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        return Ok(Value::new(ValueCore::Int(map.len() as i64), ty));
+    }
+}
+
+pub struct Contains {}
+
+impl ExternFunction for Contains {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        let key = interpreter.create_key(environment.get_arg_by_index(1));
+        return Ok(interpreter.get_bool_value(map.contains_key(&key)));
+    }
+}
+
+pub struct Keys {}
+
+impl ExternFunction for Keys {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        let keys = map.into_iter().map(|(key, _)| key.value).collect();
+        return Ok(Value::new(ValueCore::List(keys), ty));
+    }
+}
+
+pub struct Values {}
+
+impl ExternFunction for Values {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        let values = map.into_iter().map(|(_, value)| value).collect();
+        return Ok(Value::new(ValueCore::List(values), ty));
+    }
+}
+
+pub struct ToList {}
+
+impl ExternFunction for ToList {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        let tuple_ty = ty.get_type_args().remove(0);
+        let items = map
+            .into_iter()
+            .map(|(key, value)| {
+                Value::new(ValueCore::Tuple(vec![key.value, value]), tuple_ty.clone())
+            })
+            .collect();
+        return Ok(Value::new(ValueCore::List(items), ty));
+    }
+}
+
+pub struct FromList {}
+
+impl ExternFunction for FromList {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let list = environment.get_arg_by_index(0).core.as_list();
+        let mut map = OrdMap::new();
+        for item in list {
+            let mut items = item.core.as_tuple();
+            let value = items.remove(1);
+            let key = items.remove(0);
+            map.insert(interpreter.create_key(key), value);
+        }
+        return Ok(Value::new(ValueCore::Map(map), ty));
+    }
+}
+
+pub struct FoldWithKey {}
+
+impl ExternFunction for FoldWithKey {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let f = environment.get_arg_by_index(0);
+        let mut acc = environment.get_arg_by_index(1);
+        let map = environment.get_arg_by_index(2).core.as_map();
+        for (key, value) in map {
+            let args = vec![acc, key.value, value];
+            acc = interpreter.call_function_value(f.clone(), args, current_expr)?;
+        }
+        return Ok(acc);
+    }
+}
+
+pub struct MapValues {}
+
+impl ExternFunction for MapValues {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let f = environment.get_arg_by_index(0);
+        let map = environment.get_arg_by_index(1).core.as_map();
+        let mut result = OrdMap::new();
+        for (key, value) in map {
+            let value = interpreter.call_function_value(f.clone(), vec![value], current_expr)?;
+            result.insert(key, value);
+        }
+        return Ok(Value::new(ValueCore::Map(result), ty));
+    }
+}
+
+pub struct Filter {}
+
+impl ExternFunction for Filter {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let f = environment.get_arg_by_index(0);
+        let map = environment.get_arg_by_index(1).core.as_map();
+        let mut result = OrdMap::new();
+        for (key, value) in map {
+            let keep = interpreter.call_function_value(
+                f.clone(),
+                vec![key.value.clone(), value.clone()],
+                current_expr,
+            )?;
+            if keep.core.as_bool() {
+                result.insert(key, value);
+            }
+        }
+        return Ok(Value::new(ValueCore::Map(result), ty));
+    }
+}
+
+pub struct Union {}
+
+impl ExternFunction for Union {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let mut map = environment.get_arg_by_index(0).core.as_map();
+        let other = environment.get_arg_by_index(1).core.as_map();
+        for (key, value) in other {
+            map.entry(key).or_insert(value);
+        }
+        return Ok(Value::new(ValueCore::Map(map), ty));
+    }
+}
+
+pub struct Alter {}
+
+impl ExternFunction for Alter {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let f = environment.get_arg_by_index(0);
+        let key = interpreter.create_key(environment.get_arg_by_index(1));
+        let mut map = environment.get_arg_by_index(2).core.as_map();
+        let mut map_type_args = ty.get_type_args();
+        let old = match map.remove(&key) {
+            Some(v) => create_some(interpreter, v),
+            None => create_none(interpreter, map_type_args.remove(1)),
+        };
+        let new = interpreter.call_function_value(f, vec![old], current_expr)?;
+        if let Some(value) = new.core.as_option(0, 1) {
+            map.insert(key, value);
+        }
+        return Ok(Value::new(ValueCore::Map(map), ty));
+    }
+}
+
+pub struct Update {}
+
+impl ExternFunction for Update {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let f = environment.get_arg_by_index(0);
+        let key = interpreter.create_key(environment.get_arg_by_index(1));
+        let mut map = environment.get_arg_by_index(2).core.as_map();
+        if let Some(old) = map.remove(&key) {
+            let new = interpreter.call_function_value(f, vec![old], current_expr)?;
+            if let Some(value) = new.core.as_option(0, 1) {
+                map.insert(key, value);
+            }
+        }
+        return Ok(Value::new(ValueCore::Map(map), ty));
+    }
+}
+
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(MAP_MODULE_NAME, "empty", Box::new(Empty {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "insert", Box::new(Insert {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "remove", Box::new(Remove {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "get", Box::new(Get {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "show", Box::new(Show {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "opEq", Box::new(MapPartialEq {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "size", Box::new(Size {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "contains", Box::new(Contains {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "keys", Box::new(Keys {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "values", Box::new(Values {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "toList", Box::new(ToList {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "fromList", Box::new(FromList {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "foldWithKey", Box::new(FoldWithKey {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "mapValues", Box::new(MapValues {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "filter", Box::new(Filter {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "union", Box::new(Union {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "alter", Box::new(Alter {}));
+    registry.add_extern_function(MAP_MODULE_NAME, "update", Box::new(Update {}));
 }
@@ -0,0 +1,249 @@
+use crate::environment::Environment;
+use crate::error::RuntimeError;
+use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
+use crate::interpreter::Interpreter;
+use crate::value::Value;
+use crate::value::ValueCore;
+use im_rc::OrdSet;
+use siko_constants::SET_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+
+pub struct Show {}
+
+impl ExternFunction for Show {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        let mut subs = Vec::new();
+        for item in set {
+            let s = interpreter.call_show(item.value)?;
+            subs.push(s);
+        }
+        return Ok(Value::new(
+            ValueCore::String(format!("{{{}}}", subs.join(", "))),
+            ty,
+        ));
+    }
+}
+
+pub struct SetPartialEq {}
+
+impl ExternFunction for SetPartialEq {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s1 = environment.get_arg_by_index(0).core.as_set();
+        let s2 = environment.get_arg_by_index(1).core.as_set();
+        if s1.len() != s2.len() {
+            return Ok(interpreter.get_bool_value(false));
+        }
+        for (item1, item2) in s1.into_iter().zip(s2.into_iter()) {
+            let value = interpreter.call_op_partial_eq(item1.value, item2.value)?;
+            if !value.core.as_bool() {
+                return Ok(interpreter.get_bool_value(false));
+            }
+        }
+        return Ok(interpreter.get_bool_value(true));
+    }
+}
+
+pub struct Empty {}
+
+impl ExternFunction for Empty {
+    fn call(
+        &self,
+        _: &Interpreter,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        return Ok(Value::new(ValueCore::Set(OrdSet::new()), ty));
+    }
+}
+
+pub struct Insert {}
+
+impl ExternFunction for Insert {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let mut set = environment.get_arg_by_index(0).core.as_set();
+        let item = interpreter.create_key(environment.get_arg_by_index(1));
+        set.insert(item);
+        return Ok(Value::new(ValueCore::Set(set), ty));
+    }
+}
+
+pub struct Remove {}
+
+impl ExternFunction for Remove {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let mut set = environment.get_arg_by_index(0).core.as_set();
+        let item = interpreter.create_key(environment.get_arg_by_index(1));
+        set.remove(&item);
+        return Ok(Value::new(ValueCore::Set(set), ty));
+    }
+}
+
+pub struct Contains {}
+
+impl ExternFunction for Contains {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        let item = interpreter.create_key(environment.get_arg_by_index(1));
+        return Ok(interpreter.get_bool_value(set.contains(&item)));
+    }
+}
+
+pub struct Union {}
+
+impl ExternFunction for Union {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s1 = environment.get_arg_by_index(0).core.as_set();
+        let s2 = environment.get_arg_by_index(1).core.as_set();
+        return Ok(Value::new(ValueCore::Set(s1.union(s2)), ty));
+    }
+}
+
+pub struct Intersection {}
+
+impl ExternFunction for Intersection {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s1 = environment.get_arg_by_index(0).core.as_set();
+        let s2 = environment.get_arg_by_index(1).core.as_set();
+        return Ok(Value::new(ValueCore::Set(s1.intersection(s2)), ty));
+    }
+}
+
+pub struct Difference {}
+
+impl ExternFunction for Difference {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s1 = environment.get_arg_by_index(0).core.as_set();
+        let s2 = environment.get_arg_by_index(1).core.as_set();
+        return Ok(Value::new(ValueCore::Set(s1.relative_complement(s2)), ty));
+    }
+}
+
+pub struct ToList {}
+
+impl ExternFunction for ToList {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        let items = set.into_iter().map(|item| item.value).collect();
+        return Ok(Value::new(ValueCore::List(items), ty));
+    }
+}
+
+pub struct FromList {}
+
+impl ExternFunction for FromList {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let list = environment.get_arg_by_index(0).core.as_list();
+        let set = list
+            .into_iter()
+            .map(|item| interpreter.create_key(item))
+            .collect();
+        return Ok(Value::new(ValueCore::Set(set), ty));
+    }
+}
+
+pub struct Size {}
+
+impl ExternFunction for Size {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        return Ok(Value::new(ValueCore::Int(set.len() as i64), ty));
+    }
+}
+
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(SET_MODULE_NAME, "show", Box::new(Show {}));
+    registry.add_extern_function(SET_MODULE_NAME, "opEq", Box::new(SetPartialEq {}));
+    registry.add_extern_function(SET_MODULE_NAME, "empty", Box::new(Empty {}));
+    registry.add_extern_function(SET_MODULE_NAME, "insert", Box::new(Insert {}));
+    registry.add_extern_function(SET_MODULE_NAME, "remove", Box::new(Remove {}));
+    registry.add_extern_function(SET_MODULE_NAME, "contains", Box::new(Contains {}));
+    registry.add_extern_function(SET_MODULE_NAME, "union", Box::new(Union {}));
+    registry.add_extern_function(SET_MODULE_NAME, "intersection", Box::new(Intersection {}));
+    registry.add_extern_function(SET_MODULE_NAME, "difference", Box::new(Difference {}));
+    registry.add_extern_function(SET_MODULE_NAME, "toList", Box::new(ToList {}));
+    registry.add_extern_function(SET_MODULE_NAME, "fromList", Box::new(FromList {}));
+    registry.add_extern_function(SET_MODULE_NAME, "size", Box::new(Size {}));
+}
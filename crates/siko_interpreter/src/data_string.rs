@@ -1,6 +1,10 @@
 use crate::environment::Environment;
+use crate::error::RuntimeError;
 use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
 use crate::interpreter::Interpreter;
+use crate::util::create_none;
+use crate::util::create_some;
 use crate::util::get_opt_ordering_value;
 use crate::util::get_ordering_value;
 use crate::value::Value;
@@ -15,14 +19,15 @@ pub struct StringAdd {}
 impl ExternFunction for StringAdd {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_string();
         let r = environment.get_arg_by_index(1).core.as_string();
-        return Value::new(ValueCore::String(l + &r), ty);
+        return Ok(Value::new(ValueCore::String(l + &r), ty));
     }
 }
 
@@ -31,14 +36,15 @@ pub struct StringPartialEq {}
 impl ExternFunction for StringPartialEq {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_string();
         let r = environment.get_arg_by_index(1).core.as_string();
-        return Interpreter::get_bool_value(l == r);
+        return Ok(interpreter.get_bool_value(l == r));
     }
 }
 
@@ -47,15 +53,16 @@ pub struct StringPartialOrd {}
 impl ExternFunction for StringPartialOrd {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_string();
         let r = environment.get_arg_by_index(1).core.as_string();
         let ord = l.partial_cmp(&r);
-        return get_opt_ordering_value(ord);
+        return Ok(get_opt_ordering_value(interpreter, ord));
     }
 }
 
@@ -64,15 +71,16 @@ pub struct StringOrd {}
 impl ExternFunction for StringOrd {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_string();
         let r = environment.get_arg_by_index(1).core.as_string();
         let ord = l.cmp(&r);
-        return get_ordering_value(ord);
+        return Ok(get_ordering_value(interpreter, ord));
     }
 }
 
@@ -81,20 +89,333 @@ pub struct StringShow {}
 impl ExternFunction for StringShow {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let value = environment.get_arg_by_index(0).core.as_string();
-        return Value::new(ValueCore::String(value.to_string()), ty);
+        return Ok(Value::new(ValueCore::String(value.to_string()), ty));
     }
 }
 
-pub fn register_extern_functions(interpreter: &mut Interpreter) {
-    interpreter.add_extern_function(STRING_MODULE_NAME, "opAdd", Box::new(StringAdd {}));
-    interpreter.add_extern_function(STRING_MODULE_NAME, "opEq", Box::new(StringPartialEq {}));
-    interpreter.add_extern_function(STRING_MODULE_NAME, "partialCmp", Box::new(StringPartialOrd {}));
-    interpreter.add_extern_function(STRING_MODULE_NAME, "cmp", Box::new(StringOrd {}));
-    interpreter.add_extern_function(STRING_MODULE_NAME, "show", Box::new(StringShow {}));
+fn create_string_list(interpreter: &Interpreter, items: Vec<String>, ty: Type) -> Value {
+    let string_ty = interpreter.get_string_type();
+    let items = items
+        .into_iter()
+        .map(|item| Value::new(ValueCore::String(item), string_ty.clone()))
+        .collect();
+    return Value::new(ValueCore::List(items), ty);
+}
+
+pub struct StringLength {}
+
+impl ExternFunction for StringLength {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        return Ok(Value::new(ValueCore::Int(s.chars().count() as i64), ty));
+    }
+}
+
+pub struct StringSubstring {}
+
+impl ExternFunction for StringSubstring {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let start = environment.get_arg_by_index(1).core.as_int().max(0) as usize;
+        let end = environment.get_arg_by_index(2).core.as_int().max(0) as usize;
+        let sub: String = if start < end {
+            s.chars().skip(start).take(end - start).collect()
+        } else {
+            String::new()
+        };
+        return Ok(Value::new(ValueCore::String(sub), ty));
+    }
+}
+
+pub struct StringSplit {}
+
+impl ExternFunction for StringSplit {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let separator = environment.get_arg_by_index(1).core.as_string();
+        let parts = if separator.is_empty() {
+            s.chars().map(|c| c.to_string()).collect()
+        } else {
+            s.split(separator.as_str()).map(|p| p.to_string()).collect()
+        };
+        return Ok(create_string_list(interpreter, parts, ty));
+    }
+}
+
+pub struct StringTrim {}
+
+impl ExternFunction for StringTrim {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        return Ok(Value::new(ValueCore::String(s.trim().to_string()), ty));
+    }
+}
+
+pub struct StringStartsWith {}
+
+impl ExternFunction for StringStartsWith {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let prefix = environment.get_arg_by_index(1).core.as_string();
+        return Ok(interpreter.get_bool_value(s.starts_with(prefix.as_str())));
+    }
+}
+
+pub struct StringEndsWith {}
+
+impl ExternFunction for StringEndsWith {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let suffix = environment.get_arg_by_index(1).core.as_string();
+        return Ok(interpreter.get_bool_value(s.ends_with(suffix.as_str())));
+    }
+}
+
+pub struct StringReplace {}
+
+impl ExternFunction for StringReplace {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let from = environment.get_arg_by_index(1).core.as_string();
+        let to = environment.get_arg_by_index(2).core.as_string();
+        return Ok(Value::new(
+            ValueCore::String(s.replace(from.as_str(), &to)),
+            ty,
+        ));
+    }
+}
+
+pub struct StringToUpper {}
+
+impl ExternFunction for StringToUpper {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        return Ok(Value::new(ValueCore::String(s.to_uppercase()), ty));
+    }
+}
+
+pub struct StringToLower {}
+
+impl ExternFunction for StringToLower {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        return Ok(Value::new(ValueCore::String(s.to_lowercase()), ty));
+    }
+}
+
+pub struct StringChars {}
+
+impl ExternFunction for StringChars {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let chars = s.chars().map(|c| c.to_string()).collect();
+        return Ok(create_string_list(interpreter, chars, ty));
+    }
+}
+
+pub struct StringLines {}
+
+impl ExternFunction for StringLines {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let lines = s.lines().map(|l| l.to_string()).collect();
+        return Ok(create_string_list(interpreter, lines, ty));
+    }
+}
+
+pub struct StringIndexOf {}
+
+impl ExternFunction for StringIndexOf {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let pattern = environment.get_arg_by_index(1).core.as_string();
+        match s.find(pattern.as_str()) {
+            Some(byte_index) => {
+                let index = s[..byte_index].chars().count() as i64;
+                let value = Value::new(ValueCore::Int(index), interpreter.get_int_type());
+                return Ok(create_some(interpreter, value));
+            }
+            None => return Ok(create_none(interpreter, interpreter.get_int_type())),
+        }
+    }
+}
+
+pub struct StringParseInt {}
+
+impl ExternFunction for StringParseInt {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let int_ty = ty.get_type_args().remove(0);
+        match s.parse::<i64>() {
+            Ok(v) => {
+                return Ok(create_some(
+                    interpreter,
+                    Value::new(ValueCore::Int(v), int_ty),
+                ))
+            }
+            Err(_) => return Ok(create_none(interpreter, int_ty)),
+        }
+    }
+}
+
+pub struct StringParseFloat {}
+
+impl ExternFunction for StringParseFloat {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let s = environment.get_arg_by_index(0).core.as_string();
+        let float_ty = ty.get_type_args().remove(0);
+        match s.parse::<f64>() {
+            Ok(v) => {
+                return Ok(create_some(
+                    interpreter,
+                    Value::new(ValueCore::Float(v), float_ty),
+                ))
+            }
+            Err(_) => return Ok(create_none(interpreter, float_ty)),
+        }
+    }
+}
+
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(STRING_MODULE_NAME, "opAdd", Box::new(StringAdd {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "opEq", Box::new(StringPartialEq {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "partialCmp", Box::new(StringPartialOrd {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "cmp", Box::new(StringOrd {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "show", Box::new(StringShow {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "length", Box::new(StringLength {}));
+    registry.add_extern_function(
+        STRING_MODULE_NAME,
+        "substring",
+        Box::new(StringSubstring {}),
+    );
+    registry.add_extern_function(STRING_MODULE_NAME, "split", Box::new(StringSplit {}));
+    registry.add_native_function(
+        STRING_MODULE_NAME,
+        "join",
+        |parts: Vec<String>, separator: String| parts.join(&separator),
+    );
+    registry.add_extern_function(STRING_MODULE_NAME, "trim", Box::new(StringTrim {}));
+    registry.add_extern_function(
+        STRING_MODULE_NAME,
+        "startsWith",
+        Box::new(StringStartsWith {}),
+    );
+    registry.add_extern_function(STRING_MODULE_NAME, "endsWith", Box::new(StringEndsWith {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "replace", Box::new(StringReplace {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "toUpper", Box::new(StringToUpper {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "toLower", Box::new(StringToLower {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "chars", Box::new(StringChars {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "lines", Box::new(StringLines {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "indexOf", Box::new(StringIndexOf {}));
+    registry.add_extern_function(STRING_MODULE_NAME, "parseInt", Box::new(StringParseInt {}));
+    registry.add_extern_function(
+        STRING_MODULE_NAME,
+        "parseFloat",
+        Box::new(StringParseFloat {}),
+    );
 }
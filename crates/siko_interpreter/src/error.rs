@@ -0,0 +1,25 @@
+use siko_location_info::location_id::LocationId;
+
+#[derive(Debug, Clone)]
+pub struct StackFrame {
+    pub function: String,
+    pub call_location: Option<LocationId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuntimeError {
+    pub message: String,
+    pub location_id: Option<LocationId>,
+    pub stack: Vec<StackFrame>,
+}
+
+impl RuntimeError {
+    /// An error without a location, e.g. for native functions which do not know where they were called.
+    pub fn new(message: String) -> RuntimeError {
+        RuntimeError {
+            message: message,
+            location_id: None,
+            stack: Vec::new(),
+        }
+    }
+}
@@ -1,15 +1,159 @@
+use crate::convert::FromValue;
+use crate::convert::IntoValue;
 use crate::environment::Environment;
+use crate::error::RuntimeError;
+use crate::interpreter::Interpreter;
 use crate::value::Value;
 use siko_ir::expr::ExprId;
 use siko_ir::function::NamedFunctionKind;
 use siko_ir::types::Type;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
 
 pub trait ExternFunction {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         current_expr: Option<ExprId>,
         kind: &NamedFunctionKind,
         ty: Type,
-    ) -> Value;
+    ) -> Result<Value, RuntimeError>;
+}
+
+/// The result of a native function, either a plain value or a value which can fail.
+pub trait NativeResult {
+    fn into_result(self, interpreter: &Interpreter, ty: Type) -> Result<Value, RuntimeError>;
+}
+
+impl<T: IntoValue> NativeResult for T {
+    fn into_result(self, interpreter: &Interpreter, ty: Type) -> Result<Value, RuntimeError> {
+        Ok(self.into_value(interpreter, ty))
+    }
+}
+
+impl<T: IntoValue> NativeResult for Result<T, RuntimeError> {
+    fn into_result(self, interpreter: &Interpreter, ty: Type) -> Result<Value, RuntimeError> {
+        self.map(|v| v.into_value(interpreter, ty))
+    }
+}
+
+/// A plain Rust function whose arguments and result are converted
+/// with FromValue and NativeResult. Args is the tuple of the argument types.
+pub trait NativeFunction<Args> {
+    fn call_native(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        ty: Type,
+    ) -> Result<Value, RuntimeError>;
+}
+
+macro_rules! impl_native_function {
+    ($($name:ident $index:tt),*) => {
+        impl<F, R, $($name),*> NativeFunction<($($name,)*)> for F
+        where
+            F: Fn($($name),*) -> R,
+            R: NativeResult,
+            $($name: FromValue),*
+        {
+            #[allow(unused_variables)]
+            fn call_native(
+                &self,
+                interpreter: &Interpreter,
+                environment: &mut Environment,
+                ty: Type,
+            ) -> Result<Value, RuntimeError> {
+                let result = self($($name::from_value(
+                    interpreter,
+                    environment.get_arg_by_index($index),
+                )),*);
+                result.into_result(interpreter, ty)
+            }
+        }
+    };
+}
+
+impl_native_function!();
+impl_native_function!(A 0);
+impl_native_function!(A 0, B 1);
+impl_native_function!(A 0, B 1, C 2);
+impl_native_function!(A 0, B 1, C 2, D 3);
+
+pub struct NativeExtern<F, Args> {
+    function: F,
+    args: PhantomData<fn(Args)>,
+}
+
+impl<F, Args> NativeExtern<F, Args> {
+    pub fn new(function: F) -> NativeExtern<F, Args> {
+        NativeExtern {
+            function: function,
+            args: PhantomData,
+        }
+    }
+}
+
+impl<F: NativeFunction<Args>, Args> ExternFunction for NativeExtern<F, Args> {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        // the native function cannot know its location, the error gets the location of the call
+        return self
+            .function
+            .call_native(interpreter, environment, ty)
+            .map_err(|e| interpreter.runtime_error(e.message, current_expr));
+    }
+}
+
+/// The implementations of the extern functions, keyed by module and function name.
+pub struct ExternRegistry {
+    functions: BTreeMap<(String, String), Box<dyn ExternFunction>>,
+}
+
+impl ExternRegistry {
+    pub fn new() -> ExternRegistry {
+        ExternRegistry {
+            functions: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_extern_function(
+        &mut self,
+        module: &str,
+        name: &str,
+        extern_function: Box<dyn ExternFunction>,
+    ) {
+        self.functions
+            .insert((module.to_string(), name.to_string()), extern_function);
+    }
+
+    /// Registers a plain Rust function as an extern, e.g. `|a: i64, b: i64| a & b`.
+    /// The function can also return a `Result<R, RuntimeError>` to fail with a runtime error.
+    pub fn add_native_function<F, Args>(&mut self, module: &str, name: &str, function: F)
+    where
+        F: NativeFunction<Args> + 'static,
+        Args: 'static,
+    {
+        self.add_extern_function(module, name, Box::new(NativeExtern::new(function)));
+    }
+
+    pub fn get(&self, module: &str, name: &str) -> Option<&dyn ExternFunction> {
+        self.functions
+            .get(&(module.to_string(), name.to_string()))
+            .map(|f| f.as_ref())
+    }
+
+    pub fn contains(&self, module: &str, name: &str) -> bool {
+        self.get(module, name).is_some()
+    }
+
+    pub fn get_names(&self) -> Vec<(String, String)> {
+        self.functions.keys().cloned().collect()
+    }
 }
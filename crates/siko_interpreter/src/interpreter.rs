@@ -1,10 +1,19 @@
+use crate::actor_runtime::ActorRuntime;
+use crate::actor_runtime::Message;
+use crate::data_char;
 use crate::data_float;
 use crate::data_int;
 use crate::data_list;
 use crate::data_map;
+use crate::data_set;
 use crate::data_string;
 use crate::environment::Environment;
-use crate::extern_function::ExternFunction;
+use crate::error::RuntimeError;
+use crate::error::StackFrame;
+use crate::extern_function::ExternRegistry;
+use crate::std_actor;
+use crate::std_env;
+use crate::std_io_file;
 use crate::std_ops;
 use crate::std_util;
 use crate::std_util_basic;
@@ -13,20 +22,27 @@ use crate::util::get_ordering_value;
 use crate::value::BuiltinCallable;
 use crate::value::Callable;
 use crate::value::CallableKind;
+use crate::value::Key;
 use crate::value::Value;
 use crate::value::ValueCore;
-use siko_constants::MAIN_FUNCTION;
-use siko_constants::MAIN_MODULE;
+use im_rc::Vector;
+use siko_constants::IO_ERROR_TYPE_NAME;
+use siko_constants::IO_FILE_MODULE_NAME;
 use siko_constants::OPTION_MODULE_NAME;
 use siko_constants::OPTION_TYPE_NAME;
 use siko_constants::ORDERING_MODULE_NAME;
 use siko_constants::ORDERING_TYPE_NAME;
+use siko_constants::RESULT_MODULE_NAME;
+use siko_constants::RESULT_TYPE_NAME;
 use siko_ir::class::ClassMember;
 use siko_ir::class::ClassMemberId;
 use siko_ir::data::Adt;
 use siko_ir::data::TypeDefId;
 use siko_ir::expr::Expr;
 use siko_ir::expr::ExprId;
+use siko_ir::expr::FieldAccessInfo;
+use siko_ir::expr::RecordFieldValueExpr;
+use siko_ir::expr::RecordUpdateInfo;
 use siko_ir::function::FunctionId;
 use siko_ir::function::FunctionInfo;
 use siko_ir::function::NamedFunctionKind;
@@ -36,15 +52,11 @@ use siko_ir::pattern::PatternId;
 use siko_ir::program::Program;
 use siko_ir::types::Type;
 use siko_ir::unifier::Unifier;
-use siko_location_info::error_context::ErrorContext;
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
-use std::thread_local;
-
-thread_local! {
-    static INTERPRETER_CONTEXT: RefCell<Option<Interpreter>> = RefCell::new(None);
-}
+use std::rc::Rc;
+use std::rc::Weak;
 
 #[derive(Clone)]
 pub struct VariantCache {
@@ -69,28 +81,83 @@ impl VariantCache {
 pub struct TypeDefIdCache {
     pub option_id: TypeDefId,
     pub ordering_id: TypeDefId,
+    pub result_id: TypeDefId,
+    pub io_error_id: TypeDefId,
     pub option_variants: VariantCache,
     pub ordering_variants: VariantCache,
+    pub result_variants: VariantCache,
+    pub io_error_variants: VariantCache,
+}
+
+const STACK_RED_ZONE: usize = 256 * 1024;
+const STACK_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
+
+// A call in tail position is handed back to the caller's loop
+// instead of being evaluated on top of the current Rust stack.
+enum EvalResult {
+    Value(Value),
+    TailCall(Value, Vec<Value>, ExprId),
 }
 
+/// Interpreters are independent of each other, but an interpreter and its values are not Send,
+/// they stay on the thread that created them.
 pub struct Interpreter {
     program: Program,
-    error_context: ErrorContext,
+    max_call_depth: usize,
+    wrapping_arithmetic: bool,
+    program_args: Vec<String>,
     typedefid_cache: Option<TypeDefIdCache>,
-    extern_functions: BTreeMap<(String, String), Box<dyn ExternFunction>>,
+    extern_registry: ExternRegistry,
+    actor_runtime: RefCell<ActorRuntime>,
+    call_stack: RefCell<Vec<(FunctionId, Option<ExprId>)>>,
+    pending_error: RefCell<Option<RuntimeError>>,
+    self_ref: Weak<Interpreter>,
 }
 
 impl Interpreter {
-    fn new(program: Program, error_context: ErrorContext) -> Interpreter {
+    fn new(
+        program: Program,
+        max_call_depth: usize,
+        wrapping_arithmetic: bool,
+        program_args: Vec<String>,
+        extern_registry: ExternRegistry,
+        self_ref: Weak<Interpreter>,
+    ) -> Interpreter {
         Interpreter {
             program: program,
-            error_context: error_context,
+            max_call_depth: max_call_depth,
+            wrapping_arithmetic: wrapping_arithmetic,
+            program_args: program_args,
             typedefid_cache: None,
-            extern_functions: BTreeMap::new(),
+            extern_registry: extern_registry,
+            actor_runtime: RefCell::new(ActorRuntime::new()),
+            call_stack: RefCell::new(Vec::new()),
+            pending_error: RefCell::new(None),
+            self_ref: self_ref,
         }
     }
 
-    fn call(&self, callable_value: Value, args: Vec<Value>, expr_id: Option<ExprId>) -> Value {
+    // Every non tail call goes through here, when the native stack runs low
+    // the evaluation continues on a new stack segment allocated on the heap,
+    // so the host thread does not need a large stack for deep recursion.
+    fn call(
+        &self,
+        callable_value: Value,
+        args: Vec<Value>,
+        expr_id: Option<ExprId>,
+    ) -> Result<Value, RuntimeError> {
+        stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || {
+            self.call_callable(callable_value, args, expr_id)
+        })
+    }
+
+    fn call_callable(
+        &self,
+        callable_value: Value,
+        args: Vec<Value>,
+        expr_id: Option<ExprId>,
+    ) -> Result<Value, RuntimeError> {
+        let mut expr_id = expr_id;
         match callable_value.core {
             ValueCore::Callable(mut callable) => {
                 let mut callable_func_ty = callable_value.ty;
@@ -102,6 +169,8 @@ impl Interpreter {
                             BuiltinCallable::PartialEq => (2, 0),
                             BuiltinCallable::PartialOrd => (2, 0),
                             BuiltinCallable::Ord => (2, 0),
+                            BuiltinCallable::ToInt => (1, 0),
+                            BuiltinCallable::FromInt => (1, 0),
                         },
                         CallableKind::FunctionId(function_id) => {
                             let func_info = self.program.functions.get(function_id);
@@ -111,7 +180,7 @@ impl Interpreter {
                         }
                     };
                     if needed_arg_count > callable.values.len() {
-                        return Value::new(ValueCore::Callable(callable), callable_func_ty);
+                        return Ok(Value::new(ValueCore::Callable(callable), callable_func_ty));
                     } else {
                         let rest = callable.values.split_off(needed_arg_count);
                         let mut call_args = Vec::new();
@@ -121,20 +190,44 @@ impl Interpreter {
                             Environment::new(callable.kind, call_args, implicit_arg_count);
                         callable_func_ty = callable_func_ty.get_result_type(arg_count);
                         let result = match &callable.kind {
-                            CallableKind::Builtin(builtin) => self.execute_builtin(
-                                builtin,
-                                &mut environment,
-                                expr_id,
-                                &callable.unifier,
-                                callable_func_ty.clone(),
-                            ),
-                            CallableKind::FunctionId(id) => self.execute(
-                                *id,
-                                &mut environment,
-                                expr_id,
-                                &callable.unifier,
-                                callable_func_ty.clone(),
-                            ),
+                            CallableKind::Builtin(builtin) => self
+                                .execute_builtin(
+                                    builtin,
+                                    &mut environment,
+                                    expr_id,
+                                    &callable.unifier,
+                                    callable_func_ty.clone(),
+                                )
+                                .map(EvalResult::Value),
+                            CallableKind::FunctionId(id) => {
+                                self.push_frame(*id, expr_id)?;
+                                let result = self.execute(
+                                    *id,
+                                    &mut environment,
+                                    expr_id,
+                                    &callable.unifier,
+                                    callable_func_ty.clone(),
+                                );
+                                self.pop_frame();
+                                result
+                            }
+                        };
+                        let result = match result? {
+                            EvalResult::Value(result) => result,
+                            EvalResult::TailCall(tail_callable, tail_args, tail_expr_id) => {
+                                // continue with the tail call in this loop, the remaining
+                                // arguments are applied to its result
+                                if let ValueCore::Callable(new_callable) = tail_callable.core {
+                                    callable = new_callable;
+                                    callable_func_ty = tail_callable.ty;
+                                    callable.values.extend(tail_args);
+                                    callable.values.extend(rest);
+                                    expr_id = Some(tail_expr_id);
+                                    continue;
+                                } else {
+                                    panic!("Cannot call {:?}", tail_callable);
+                                }
+                            }
                         };
                         if !rest.is_empty() {
                             if let ValueCore::Callable(new_callable) = result.core {
@@ -145,7 +238,7 @@ impl Interpreter {
                                 unreachable!()
                             }
                         } else {
-                            return result;
+                            return Ok(result);
                         }
                     }
                 }
@@ -160,25 +253,25 @@ impl Interpreter {
         value: &Value,
         environment: &mut Environment,
         unifier: &Unifier,
-    ) -> bool {
+    ) -> Result<bool, RuntimeError> {
         let pattern = &self.program.patterns.get(pattern_id).item;
         match pattern {
             Pattern::Binding(_) => {
                 environment.add(*pattern_id, value.clone());
-                return true;
+                return Ok(true);
             }
             Pattern::Tuple(ids) => match &value.core {
                 ValueCore::Tuple(vs) => {
                     for (index, id) in ids.iter().enumerate() {
                         let v = &vs[index];
-                        if !self.match_pattern(id, v, environment, unifier) {
-                            return false;
+                        if !self.match_pattern(id, v, environment, unifier)? {
+                            return Ok(false);
                         }
                     }
-                    return true;
+                    return Ok(true);
                 }
                 _ => {
-                    return false;
+                    return Ok(false);
                 }
             },
             Pattern::Record(p_type_id, p_ids) => match &value.core {
@@ -186,16 +279,16 @@ impl Interpreter {
                     if type_id == p_type_id {
                         for (index, p_id) in p_ids.iter().enumerate() {
                             let v = &vs[index];
-                            if !self.match_pattern(p_id, v, environment, unifier) {
-                                return false;
+                            if !self.match_pattern(p_id, v, environment, unifier)? {
+                                return Ok(false);
                             }
                         }
-                        return true;
+                        return Ok(true);
                     }
-                    return false;
+                    return Ok(false);
                 }
                 _ => {
-                    return false;
+                    return Ok(false);
                 }
             },
             Pattern::Variant(p_type_id, p_index, p_ids) => match &value.core {
@@ -203,151 +296,316 @@ impl Interpreter {
                     if type_id == p_type_id && index == p_index {
                         for (index, p_id) in p_ids.iter().enumerate() {
                             let v = &vs[index];
-                            if !self.match_pattern(p_id, v, environment, unifier) {
-                                return false;
+                            if !self.match_pattern(p_id, v, environment, unifier)? {
+                                return Ok(false);
                             }
                         }
-                        return true;
+                        return Ok(true);
                     }
-                    return false;
+                    return Ok(false);
                 }
                 _ => {
-                    return false;
+                    return Ok(false);
                 }
             },
             Pattern::Guarded(id, guard_expr_id) => {
-                if self.match_pattern(id, value, environment, unifier) {
-                    let guard_value = self.eval_expr(*guard_expr_id, environment, unifier);
-                    return guard_value.core.as_bool();
+                if self.match_pattern(id, value, environment, unifier)? {
+                    let guard_value = self.eval_expr(*guard_expr_id, environment, unifier)?;
+                    return Ok(guard_value.core.as_bool());
                 } else {
-                    return false;
+                    return Ok(false);
                 }
             }
             Pattern::Typed(id, _) => self.match_pattern(id, value, environment, unifier),
             Pattern::Wildcard => {
-                return true;
+                return Ok(true);
             }
             Pattern::IntegerLiteral(p_v) => {
                 let r = match &value.core {
                     ValueCore::Int(v) => p_v == v,
                     _ => false,
                 };
-                return r;
+                return Ok(r);
             }
             Pattern::FloatLiteral(p_v) => {
                 let r = match &value.core {
                     ValueCore::Float(v) => p_v == v,
                     _ => false,
                 };
-                return r;
+                return Ok(r);
             }
             Pattern::StringLiteral(p_v) => {
                 let r = match &value.core {
                     ValueCore::String(v) => p_v == v,
                     _ => false,
                 };
-                return r;
+                return Ok(r);
+            }
+            Pattern::CharLiteral(p_v) => {
+                let r = match &value.core {
+                    ValueCore::Char(v) => p_v == v,
+                    _ => false,
+                };
+                return Ok(r);
             }
         }
     }
 
-    pub fn call_show(arg: Value) -> String {
-        let string_ty = Interpreter::get_string_type();
-        let v = Interpreter::call_specific_class_member(vec![arg], "Show", "show", string_ty);
-        v.core.as_string()
+    pub fn call_show(&self, arg: Value) -> Result<String, RuntimeError> {
+        let string_ty = self.get_string_type();
+        let v = self.call_specific_class_member(vec![arg], "Show", "show", string_ty)?;
+        Ok(v.core.as_string())
     }
 
-    pub fn get_string_type() -> Type {
-        INTERPRETER_CONTEXT.with(|i| {
-            let b = i.borrow();
-            let i = b.as_ref().expect("Interpreter not set");
-            let string_ty = i.program.get_string_type();
-            string_ty
-        })
+    pub fn get_string_type(&self) -> Type {
+        self.program.get_string_type()
     }
 
-    pub fn get_bool_type() -> Type {
-        INTERPRETER_CONTEXT.with(|i| {
-            let b = i.borrow();
-            let i = b.as_ref().expect("Interpreter not set");
-            let bool_ty = i.program.get_bool_type();
-            bool_ty
-        })
+    pub fn get_bool_type(&self) -> Type {
+        self.program.get_bool_type()
     }
 
-    pub fn get_bool_value(v: bool) -> Value {
-        INTERPRETER_CONTEXT.with(|i| {
-            let b = i.borrow();
-            let i = b.as_ref().expect("Interpreter not set");
-            let bool_ty = i.program.get_bool_type();
-            if let Type::Named(_, id, _) = &bool_ty {
-                Value::new(
-                    ValueCore::Variant(*id, if v == true { 0 } else { 1 }, vec![]),
-                    bool_ty,
-                )
-            } else {
-                unreachable!()
-            }
-        })
+    pub fn get_int_type(&self) -> Type {
+        self.program.get_int_type()
     }
 
-    pub fn get_optional_ordering_type() -> Type {
-        INTERPRETER_CONTEXT.with(|i| {
-            let b = i.borrow();
-            let i = b.as_ref().expect("Interpreter not set");
-            let option_ordering_ty = i.program.get_option_type(i.program.get_ordering_type());
-            option_ordering_ty
-        })
+    pub fn get_bool_value(&self, v: bool) -> Value {
+        let bool_ty = self.program.get_bool_type();
+        if let Type::Named(_, id, _) = &bool_ty {
+            Value::new(
+                ValueCore::Variant(*id, if v == true { 0 } else { 1 }, vec![]),
+                bool_ty,
+            )
+        } else {
+            unreachable!()
+        }
     }
 
-    pub fn get_ordering_type() -> Type {
-        INTERPRETER_CONTEXT.with(|i| {
-            let b = i.borrow();
-            let i = b.as_ref().expect("Interpreter not set");
-            i.program.get_ordering_type()
-        })
+    pub fn get_optional_ordering_type(&self) -> Type {
+        self.program
+            .get_option_type(self.program.get_ordering_type())
+    }
+
+    pub fn get_ordering_type(&self) -> Type {
+        self.program.get_ordering_type()
     }
 
     pub fn call_specific_class_member(
+        &self,
         args: Vec<Value>,
         class_name: &str,
         member_name: &str,
         expr_ty: Type,
-    ) -> Value {
-        INTERPRETER_CONTEXT.with(|i| {
-            let b = i.borrow();
-            let i = b.as_ref().expect("Interpreter not set");
-            let class_id = i
+    ) -> Result<Value, RuntimeError> {
+        let class_id = self
+            .program
+            .class_names
+            .get(class_name)
+            .expect("Show not found");
+        let class = self.program.classes.get(class_id);
+        let class_member_id = class.members.get(member_name).expect("show not found");
+        self.call_class_member(class_member_id, args, None, expr_ty)
+    }
+
+    pub fn call_function_value(
+        &self,
+        function: Value,
+        args: Vec<Value>,
+        current_expr: Option<ExprId>,
+    ) -> Result<Value, RuntimeError> {
+        self.call(function, args, current_expr)
+    }
+
+    pub fn runtime_error(&self, message: String, current_expr: Option<ExprId>) -> RuntimeError {
+        self.create_runtime_error(message, current_expr)
+    }
+
+    pub fn set_pending_error(&self, error: RuntimeError) {
+        let mut pending_error = self.pending_error.borrow_mut();
+        if pending_error.is_none() {
+            *pending_error = Some(error);
+        }
+    }
+
+    fn get_function_name(&self, id: &FunctionId) -> String {
+        let function = self.program.functions.get(id);
+        match &function.info {
+            FunctionInfo::NamedFunction(info) => format!("{}.{}", info.module, info.name),
+            FunctionInfo::Lambda(info) => format!("{}", info),
+            FunctionInfo::RecordConstructor(info) => {
+                let (module, name) = self.program.get_module_and_name(info.type_id);
+                format!("{}.{}", module, name)
+            }
+            FunctionInfo::VariantConstructor(info) => {
+                let adt = self.program.typedefs.get(&info.type_id).get_adt();
+                format!("{}.{}", adt.module, adt.variants[info.index].name)
+            }
+        }
+    }
+
+    fn push_frame(&self, id: FunctionId, call_expr: Option<ExprId>) -> Result<(), RuntimeError> {
+        if self.call_stack.borrow().len() >= self.max_call_depth {
+            return Err(self.create_runtime_error(
+                format!("Maximum call depth of {} exceeded", self.max_call_depth),
+                call_expr,
+            ));
+        }
+        self.call_stack.borrow_mut().push((id, call_expr));
+        Ok(())
+    }
+
+    fn pop_frame(&self) {
+        self.call_stack.borrow_mut().pop();
+    }
+
+    fn create_runtime_error(&self, message: String, current_expr: Option<ExprId>) -> RuntimeError {
+        let stack: Vec<_> = self
+            .call_stack
+            .borrow()
+            .iter()
+            .rev()
+            .map(|(id, call_expr)| StackFrame {
+                function: self.get_function_name(id),
+                call_location: call_expr
+                    .map(|expr_id| self.program.exprs.get(&expr_id).location_id),
+            })
+            .collect();
+        let location_id = match current_expr {
+            Some(expr_id) => Some(self.program.exprs.get(&expr_id).location_id),
+            None => stack.iter().filter_map(|frame| frame.call_location).next(),
+        };
+        RuntimeError {
+            message: message,
+            location_id: location_id,
+            stack: stack,
+        }
+    }
+
+    pub fn spawn_actor(&self, actor: Value, current_expr: ExprId) -> Result<usize, RuntimeError> {
+        let actor_id = match &actor.core {
+            ValueCore::Variant(type_id, _, _) => self
                 .program
-                .class_names
-                .get(class_name)
-                .expect("Show not found");
-            let class = i.program.classes.get(class_id);
-            let class_member_id = class.members.get(member_name).expect("show not found");
-            let v = i.call_class_member(class_member_id, args, None, expr_ty);
-            v
-        })
+                .actors
+                .items
+                .iter()
+                .find(|(_, item)| item.type_id == *type_id)
+                .map(|(id, _)| *id),
+            _ => None,
+        };
+        let actor_id = match actor_id {
+            Some(actor_id) => actor_id,
+            None => {
+                return Err(self.create_runtime_error(
+                    format!(
+                        "Value of type {} is not an actor",
+                        actor.ty.get_resolved_type_string(&self.program)
+                    ),
+                    Some(current_expr),
+                ));
+            }
+        };
+        let state = if let ValueCore::Variant(_, _, mut items) = actor.core {
+            items.remove(0)
+        } else {
+            unreachable!()
+        };
+        let pid = self.actor_runtime.borrow_mut().spawn(actor_id, state);
+        Ok(pid)
     }
 
-    pub fn call_abort(current_expr: ExprId) {
-        INTERPRETER_CONTEXT.with(|i| {
-            let b = i.borrow();
-            let i = b.as_ref().expect("Interpreter not set");
-            let location_id = i.program.exprs.get(&current_expr).location_id;
-            i.error_context
-                .report_error(format!("Assertion failed"), location_id);
-            panic!("Abort not implemented");
-        })
+    pub fn send_message(
+        &self,
+        pid: i64,
+        message: Value,
+        current_expr: ExprId,
+    ) -> Result<(), RuntimeError> {
+        let actor_id = if pid >= 0 {
+            self.actor_runtime.borrow().get_actor_id(pid as usize)
+        } else {
+            None
+        };
+        let actor_id = match actor_id {
+            Some(actor_id) => actor_id,
+            None => {
+                return Err(
+                    self.create_runtime_error(format!("Invalid pid {}", pid), Some(current_expr))
+                );
+            }
+        };
+        let actor = self.program.actors.get(&actor_id);
+        let handler = match &message.ty {
+            Type::Named(_, id, _) => actor
+                .handlers
+                .iter()
+                .find(|handler| handler.protocol_id == *id),
+            _ => None,
+        };
+        let handler = match handler {
+            Some(handler) => handler,
+            None => {
+                return Err(self.create_runtime_error(
+                    format!(
+                        "Actor {} has no handler for message of type {}",
+                        actor.name,
+                        message.ty.get_resolved_type_string(&self.program)
+                    ),
+                    Some(current_expr),
+                ));
+            }
+        };
+        let payload = if let ValueCore::Variant(_, _, mut items) = message.core {
+            items.remove(0)
+        } else {
+            unreachable!()
+        };
+        let message = Message {
+            handler_func: handler.handler_func,
+            payload: payload,
+        };
+        self.actor_runtime.borrow_mut().send(pid as usize, message);
+        Ok(())
     }
 
-    pub fn call_op_eq(arg1: Value, arg2: Value) -> Value {
-        let bool_ty = Interpreter::get_bool_type();
-        Interpreter::call_specific_class_member(vec![arg1, arg2], "PartialEq", "opEq", bool_ty)
+    fn run_actors(&self) -> Result<(), RuntimeError> {
+        loop {
+            let next = self.actor_runtime.borrow_mut().next_message();
+            match next {
+                Some((pid, state, message)) => {
+                    let func_ty = self
+                        .program
+                        .function_types
+                        .get(&message.handler_func)
+                        .expect("untyped func")
+                        .remove_fixed_types();
+                    let state_ty = state.ty.clone();
+                    let arg_values = vec![state, message.payload];
+                    let call_unifier = self.get_call_unifier(&arg_values, &func_ty, &state_ty);
+                    let function_type = call_unifier.apply(&func_ty);
+                    let callable = Value::new(
+                        ValueCore::Callable(Callable {
+                            kind: CallableKind::FunctionId(message.handler_func),
+                            values: vec![],
+                            unifier: call_unifier,
+                        }),
+                        function_type,
+                    );
+                    let new_state = self.call(callable, arg_values, None)?;
+                    self.actor_runtime.borrow_mut().update_state(pid, new_state);
+                }
+                None => break,
+            }
+        }
+        Ok(())
     }
 
-    pub fn call_op_partial_cmp(arg1: Value, arg2: Value) -> Value {
-        let option_ordering_ty = Interpreter::get_optional_ordering_type();
-        Interpreter::call_specific_class_member(
+    pub fn call_op_eq(&self, arg1: Value, arg2: Value) -> Result<Value, RuntimeError> {
+        let bool_ty = self.get_bool_type();
+        self.call_specific_class_member(vec![arg1, arg2], "PartialEq", "opEq", bool_ty)
+    }
+
+    pub fn call_op_partial_cmp(&self, arg1: Value, arg2: Value) -> Result<Value, RuntimeError> {
+        let option_ordering_ty = self.get_optional_ordering_type();
+        self.call_specific_class_member(
             vec![arg1, arg2],
             "PartialOrd",
             "partialCmp",
@@ -355,18 +613,13 @@ impl Interpreter {
         )
     }
 
-    pub fn call_op_partial_eq(arg1: Value, arg2: Value) -> Value {
-        Interpreter::call_specific_class_member(
-            vec![arg1, arg2],
-            "PartialEq",
-            "opEq",
-            Interpreter::get_bool_type(),
-        )
+    pub fn call_op_partial_eq(&self, arg1: Value, arg2: Value) -> Result<Value, RuntimeError> {
+        self.call_specific_class_member(vec![arg1, arg2], "PartialEq", "opEq", self.get_bool_type())
     }
 
-    pub fn call_op_cmp(arg1: Value, arg2: Value) -> Value {
-        let ordering_ty = Interpreter::get_ordering_type();
-        Interpreter::call_specific_class_member(vec![arg1, arg2], "Ord", "cmp", ordering_ty)
+    pub fn call_op_cmp(&self, arg1: Value, arg2: Value) -> Result<Value, RuntimeError> {
+        let ordering_ty = self.get_ordering_type();
+        self.call_specific_class_member(vec![arg1, arg2], "Ord", "cmp", ordering_ty)
     }
 
     fn get_call_unifier(
@@ -419,7 +672,7 @@ impl Interpreter {
         arg_values: Vec<Value>,
         expr_id: Option<ExprId>,
         expr_ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         for arg in &arg_values {
             assert!(arg.ty.is_concrete_type());
         }
@@ -452,6 +705,13 @@ impl Interpreter {
                     ("Std.Ops", "Ord") => {
                         Interpreter::check_member(member, "cmp", BuiltinCallable::Ord)
                     }
+                    ("Std.Ops", "Enum") => {
+                        if member.name == "toInt" {
+                            Some(CallableKind::Builtin(BuiltinCallable::ToInt))
+                        } else {
+                            Some(CallableKind::Builtin(BuiltinCallable::FromInt))
+                        }
+                    }
                     _ => panic!(
                         "Auto derive of {}/{} is not implemented",
                         class.module, class.name
@@ -505,12 +765,14 @@ impl Interpreter {
         }
     }
 
+    // The rarely used arms are evaluated in separate functions,
+    // this keeps the stack frame of this recursive function small.
     fn eval_expr(
         &self,
         expr_id: ExprId,
         environment: &mut Environment,
         unifier: &Unifier,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let expr = &self.program.exprs.get(&expr_id).item;
         //println!("Eval {} {}", expr_id, expr);
         let expr_ty = self
@@ -521,13 +783,219 @@ impl Interpreter {
             .clone();
         let expr_ty = unifier.apply(&expr_ty);
         match expr {
-            Expr::IntegerLiteral(v) => Value::new(ValueCore::Int(*v), expr_ty),
-            Expr::StringLiteral(v) => Value::new(ValueCore::String(v.clone()), expr_ty),
-            Expr::FloatLiteral(v) => Value::new(ValueCore::Float(*v), expr_ty),
+            Expr::IntegerLiteral(v) => Ok(Value::new(ValueCore::Int(*v), expr_ty)),
+            Expr::StringLiteral(v) => Ok(Value::new(ValueCore::String(v.clone()), expr_ty)),
+            Expr::CharLiteral(v) => Ok(Value::new(ValueCore::Char(*v), expr_ty)),
+            Expr::FloatLiteral(v) => Ok(Value::new(ValueCore::Float(*v), expr_ty)),
             Expr::ArgRef(arg_ref) => {
-                return environment.get_arg(arg_ref);
+                return Ok(environment.get_arg(arg_ref));
+            }
+            Expr::StaticFunctionCall(..) | Expr::DynamicFunctionCall(..) | Expr::Do(..) => {
+                let result = self.eval_tail_expr(expr_id, environment, unifier)?;
+                return self.finish_tail_call(result);
             }
+            Expr::Bind(pattern_id, expr_id) => {
+                return self.eval_bind(*pattern_id, *expr_id, environment, unifier, expr_ty);
+            }
+            Expr::ExprValue(_, pattern_id) => {
+                return Ok(environment.get_value(pattern_id));
+            }
+            Expr::If(..) | Expr::CaseOf(..) => {
+                let result = self.eval_tail_expr(expr_id, environment, unifier)?;
+                return self.finish_tail_call(result);
+            }
+            Expr::Tuple(exprs) => {
+                let values = self.eval_exprs(exprs, environment, unifier)?;
+                return Ok(Value::new(ValueCore::Tuple(values), expr_ty));
+            }
+            Expr::List(exprs) => {
+                let values = self.eval_exprs(exprs, environment, unifier)?;
+                return Ok(Value::new(ValueCore::List(Vector::from(values)), expr_ty));
+            }
+            Expr::TupleFieldAccess(index, tuple) => {
+                let tuple_value = self.eval_expr(*tuple, environment, unifier)?;
+                if let ValueCore::Tuple(t) = &tuple_value.core {
+                    return Ok(t[*index].clone());
+                } else {
+                    unreachable!()
+                }
+            }
+            Expr::Formatter(fmt, args) => {
+                return self.eval_formatter(fmt, args, environment, unifier, expr_ty);
+            }
+            Expr::FieldAccess(infos, record_expr) => {
+                return self.eval_field_access(infos, *record_expr, environment, unifier);
+            }
+            Expr::RecordInitialization(type_id, items) => {
+                return self.eval_record_initialization(
+                    *type_id,
+                    items,
+                    environment,
+                    unifier,
+                    expr_ty,
+                );
+            }
+            Expr::RecordUpdate(record_expr_id, updates) => {
+                return self.eval_record_update(
+                    *record_expr_id,
+                    updates,
+                    environment,
+                    unifier,
+                    expr_ty,
+                );
+            }
+            Expr::ClassFunctionCall(class_member_id, args) => {
+                let arg_values = self.eval_exprs(args, environment, unifier)?;
+                return self.call_class_member(class_member_id, arg_values, Some(expr_id), expr_ty);
+            }
+        }
+    }
+
+    fn eval_exprs(
+        &self,
+        exprs: &[ExprId],
+        environment: &mut Environment,
+        unifier: &Unifier,
+    ) -> Result<Vec<Value>, RuntimeError> {
+        exprs
+            .iter()
+            .map(|e| self.eval_expr(*e, environment, unifier))
+            .collect()
+    }
+
+    #[inline(never)]
+    fn eval_bind(
+        &self,
+        pattern_id: PatternId,
+        expr_id: ExprId,
+        environment: &mut Environment,
+        unifier: &Unifier,
+        expr_ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let value = self.eval_expr(expr_id, environment, unifier)?;
+        let r = self.match_pattern(&pattern_id, &value, environment, unifier)?;
+        if !r {
+            return Err(self.create_runtime_error(format!("Pattern match failed"), Some(expr_id)));
+        }
+        Ok(Value::new(ValueCore::Tuple(vec![]), expr_ty))
+    }
+
+    #[inline(never)]
+    fn eval_formatter(
+        &self,
+        fmt: &str,
+        args: &[ExprId],
+        environment: &mut Environment,
+        unifier: &Unifier,
+        expr_ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let subs: Vec<_> = fmt.split("{}").collect();
+        let values = self.eval_exprs(args, environment, unifier)?;
+        let mut result = String::new();
+        for (index, sub) in subs.iter().enumerate() {
+            result += sub;
+            if values.len() > index {
+                let value_as_string = self.call_show(values[index].clone())?;
+                result += &value_as_string;
+            }
+        }
+        Ok(Value::new(ValueCore::String(result), expr_ty))
+    }
+
+    #[inline(never)]
+    fn eval_field_access(
+        &self,
+        infos: &[FieldAccessInfo],
+        record_expr: ExprId,
+        environment: &mut Environment,
+        unifier: &Unifier,
+    ) -> Result<Value, RuntimeError> {
+        let record = self.eval_expr(record_expr, environment, unifier)?;
+        let (id, values) = if let ValueCore::Record(id, values) = &record.core {
+            (id, values)
+        } else {
+            unreachable!()
+        };
+        for info in infos {
+            if info.record_id != *id {
+                continue;
+            }
+            return Ok(values[info.index].clone());
+        }
+        unreachable!()
+    }
+
+    #[inline(never)]
+    fn eval_record_initialization(
+        &self,
+        type_id: TypeDefId,
+        items: &[RecordFieldValueExpr],
+        environment: &mut Environment,
+        unifier: &Unifier,
+        expr_ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let mut values: Vec<_> = Vec::with_capacity(items.len());
+        for _ in 0..items.len() {
+            values.push(Value::new(ValueCore::Tuple(vec![]), expr_ty.clone()));
+            // dummy value
+        }
+        for item in items {
+            let value = self.eval_expr(item.expr_id, environment, unifier)?;
+            values[item.index] = value;
+        }
+        Ok(Value::new(ValueCore::Record(type_id, values), expr_ty))
+    }
+
+    #[inline(never)]
+    fn eval_record_update(
+        &self,
+        record_expr_id: ExprId,
+        updates: &[RecordUpdateInfo],
+        environment: &mut Environment,
+        unifier: &Unifier,
+        expr_ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let value = self.eval_expr(record_expr_id, environment, unifier)?;
+        if let ValueCore::Record(id, mut values) = value.core {
+            for update in updates {
+                if id == update.record_id {
+                    for item in &update.items {
+                        let value = self.eval_expr(item.expr_id, environment, unifier)?;
+                        values[item.index] = value;
+                    }
+                    return Ok(Value::new(ValueCore::Record(id, values), expr_ty));
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    fn finish_tail_call(&self, result: EvalResult) -> Result<Value, RuntimeError> {
+        match result {
+            EvalResult::Value(value) => Ok(value),
+            EvalResult::TailCall(callable, args, expr_id) => {
+                self.call(callable, args, Some(expr_id))
+            }
+        }
+    }
+
+    // Evaluates an expression in tail position, calls are not executed but returned
+    fn eval_tail_expr(
+        &self,
+        expr_id: ExprId,
+        environment: &mut Environment,
+        unifier: &Unifier,
+    ) -> Result<EvalResult, RuntimeError> {
+        let expr = &self.program.exprs.get(&expr_id).item;
+        match expr {
             Expr::StaticFunctionCall(function_id, args) => {
+                let expr_ty = self
+                    .program
+                    .expr_types
+                    .get(&expr_id)
+                    .expect("Untyped expr")
+                    .clone();
+                let expr_ty = unifier.apply(&expr_ty);
                 let func_ty = self
                     .program
                     .function_types
@@ -537,7 +1005,7 @@ impl Interpreter {
                 let arg_values: Vec<_> = args
                     .iter()
                     .map(|arg| self.eval_expr(*arg, environment, unifier))
-                    .collect();
+                    .collect::<Result<_, _>>()?;
                 for arg in &arg_values {
                     assert!(arg.ty.is_concrete_type());
                 }
@@ -551,143 +1019,52 @@ impl Interpreter {
                     }),
                     function_type,
                 );
-                return self.call(callable, arg_values, Some(expr_id));
+                return Ok(EvalResult::TailCall(callable, arg_values, expr_id));
             }
             Expr::DynamicFunctionCall(function_expr_id, args) => {
-                let function_expr_id = self.eval_expr(*function_expr_id, environment, unifier);
+                let function_value = self.eval_expr(*function_expr_id, environment, unifier)?;
                 let arg_values: Vec<_> = args
                     .iter()
                     .map(|arg| self.eval_expr(*arg, environment, unifier))
-                    .collect();
-                return self.call(function_expr_id, arg_values, Some(expr_id));
+                    .collect::<Result<_, _>>()?;
+                return Ok(EvalResult::TailCall(function_value, arg_values, expr_id));
             }
             Expr::Do(exprs) => {
                 let mut environment = Environment::block_child(environment);
-                let mut result = Value::new(ValueCore::Tuple(vec![]), expr_ty);
-                assert!(!exprs.is_empty());
+                let (last, exprs) = exprs.split_last().expect("Empty do block");
                 for expr in exprs {
-                    result = self.eval_expr(*expr, &mut environment, unifier);
+                    self.eval_expr(*expr, &mut environment, unifier)?;
                 }
-                return result;
-            }
-            Expr::Bind(pattern_id, expr_id) => {
-                let value = self.eval_expr(*expr_id, environment, unifier);
-                let r = self.match_pattern(pattern_id, &value, environment, unifier);
-                assert!(r);
-                return Value::new(ValueCore::Tuple(vec![]), expr_ty);
-            }
-            Expr::ExprValue(_, pattern_id) => {
-                return environment.get_value(pattern_id);
+                return self.eval_tail_expr(*last, &mut environment, unifier);
             }
             Expr::If(cond, true_branch, false_branch) => {
-                let cond_value = self.eval_expr(*cond, environment, unifier);
+                let cond_value = self.eval_expr(*cond, environment, unifier)?;
                 if cond_value.core.as_bool() {
-                    return self.eval_expr(*true_branch, environment, unifier);
-                } else {
-                    return self.eval_expr(*false_branch, environment, unifier);
-                }
-            }
-            Expr::Tuple(exprs) => {
-                let values: Vec<_> = exprs
-                    .iter()
-                    .map(|e| self.eval_expr(*e, environment, unifier))
-                    .collect();
-                return Value::new(ValueCore::Tuple(values), expr_ty);
-            }
-            Expr::List(exprs) => {
-                let values: Vec<_> = exprs
-                    .iter()
-                    .map(|e| self.eval_expr(*e, environment, unifier))
-                    .collect();
-                return Value::new(ValueCore::List(values), expr_ty);
-            }
-            Expr::TupleFieldAccess(index, tuple) => {
-                let tuple_value = self.eval_expr(*tuple, environment, unifier);
-                if let ValueCore::Tuple(t) = &tuple_value.core {
-                    return t[*index].clone();
-                } else {
-                    unreachable!()
-                }
-            }
-            Expr::Formatter(fmt, args) => {
-                let subs: Vec<_> = fmt.split("{}").collect();
-                let values: Vec<_> = args
-                    .iter()
-                    .map(|e| self.eval_expr(*e, environment, unifier))
-                    .collect();
-                let mut result = String::new();
-                for (index, sub) in subs.iter().enumerate() {
-                    result += sub;
-                    if values.len() > index {
-                        let value_as_string = Interpreter::call_show(values[index].clone());
-                        result += &value_as_string;
-                    }
-                }
-                return Value::new(ValueCore::String(result), expr_ty);
-            }
-            Expr::FieldAccess(infos, record_expr) => {
-                let record = self.eval_expr(*record_expr, environment, unifier);
-                let (id, values) = if let ValueCore::Record(id, values) = &record.core {
-                    (id, values)
+                    return self.eval_tail_expr(*true_branch, environment, unifier);
                 } else {
-                    unreachable!()
-                };
-                for info in infos {
-                    if info.record_id != *id {
-                        continue;
-                    }
-                    return values[info.index].clone();
+                    return self.eval_tail_expr(*false_branch, environment, unifier);
                 }
-                unreachable!()
             }
             Expr::CaseOf(body, cases, _) => {
-                let case_value = self.eval_expr(*body, environment, unifier);
+                let case_value = self.eval_expr(*body, environment, unifier)?;
                 for case in cases {
                     let mut case_env = Environment::block_child(environment);
-                    if self.match_pattern(&case.pattern_id, &case_value, &mut case_env, unifier) {
-                        let val = self.eval_expr(case.body, &mut case_env, unifier);
-                        return val;
+                    if self.match_pattern(&case.pattern_id, &case_value, &mut case_env, unifier)? {
+                        return self.eval_tail_expr(case.body, &mut case_env, unifier);
                     }
                 }
-                unreachable!()
-            }
-            Expr::RecordInitialization(type_id, items) => {
-                let mut values: Vec<_> = Vec::with_capacity(items.len());
-                for _ in 0..items.len() {
-                    values.push(Value::new(ValueCore::Tuple(vec![]), expr_ty.clone()));
-                    // dummy value
-                }
-                for item in items {
-                    let value = self.eval_expr(item.expr_id, environment, unifier);
-                    values[item.index] = value;
-                }
-                return Value::new(ValueCore::Record(*type_id, values), expr_ty);
-            }
-            Expr::RecordUpdate(record_expr_id, updates) => {
-                let value = self.eval_expr(*record_expr_id, environment, unifier);
-                if let ValueCore::Record(id, mut values) = value.core {
-                    for update in updates {
-                        if id == update.record_id {
-                            for item in &update.items {
-                                let value = self.eval_expr(item.expr_id, environment, unifier);
-                                values[item.index] = value;
-                            }
-                            return Value::new(ValueCore::Record(id, values), expr_ty);
-                        }
-                    }
-                }
-                unreachable!()
+                return Err(
+                    self.create_runtime_error(format!("No matching case found"), Some(expr_id))
+                );
             }
-            Expr::ClassFunctionCall(class_member_id, args) => {
-                let arg_values: Vec<_> = args
-                    .iter()
-                    .map(|e| self.eval_expr(*e, environment, unifier))
-                    .collect();
-                return self.call_class_member(class_member_id, arg_values, Some(expr_id), expr_ty);
+            _ => {
+                let value = self.eval_expr(expr_id, environment, unifier)?;
+                return Ok(EvalResult::Value(value));
             }
         }
     }
 
+    #[inline(never)]
     fn call_extern(
         &self,
         module: &str,
@@ -696,32 +1073,64 @@ impl Interpreter {
         current_expr: Option<ExprId>,
         kind: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
-        if let Some(f) = self
-            .extern_functions
-            .get(&(module.to_string(), name.to_string()))
-        {
-            return f.call(environment, current_expr, kind, ty);
+    ) -> Result<Value, RuntimeError> {
+        if let Some(f) = self.extern_registry.get(module, name) {
+            let result = f.call(self, environment, current_expr, kind, ty);
+            // errors raised inside Ord/PartialEq implementations of values
+            if let Some(error) = self.pending_error.borrow_mut().take() {
+                return Err(error);
+            }
+            return result;
         } else {
-            panic!("Unimplemented extern function {} {}", module, name);
+            return Err(self.create_runtime_error(
+                format!("Unimplemented extern function {}.{}", module, name),
+                current_expr,
+            ));
         }
     }
 
+    #[inline(never)]
     fn execute_builtin(
         &self,
         builtin: &BuiltinCallable,
         environment: &mut Environment,
-        _: Option<ExprId>,
+        current_expr: Option<ExprId>,
         _: &Unifier,
-        _: Type,
-    ) -> Value {
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
         match builtin {
+            BuiltinCallable::ToInt => {
+                let v = environment.get_arg_by_index(0);
+                let (_, index) = v.core.as_simple_enum_variant();
+                return Ok(Value::new(
+                    ValueCore::Int(index as i64),
+                    self.program.get_int_type(),
+                ));
+            }
+            BuiltinCallable::FromInt => {
+                let index = environment.get_arg_by_index(0).core.as_int();
+                let id = match &ty {
+                    Type::Named(_, id, _) => *id,
+                    _ => unreachable!(),
+                };
+                let adt = self.program.typedefs.get(&id).get_adt();
+                if index < 0 || index as usize >= adt.variants.len() {
+                    return Err(self.create_runtime_error(
+                        format!("{} has no variant with index {}", adt.name, index),
+                        current_expr,
+                    ));
+                }
+                return Ok(Value::new(
+                    ValueCore::Variant(id, index as usize, vec![]),
+                    ty,
+                ));
+            }
             BuiltinCallable::Show => {
                 let v = environment.get_arg_by_index(0);
-                return Value::new(
-                    ValueCore::String(v.core.show(&self.program)),
+                return Ok(Value::new(
+                    ValueCore::String(v.core.show(self)?),
                     self.program.get_string_type(),
-                );
+                ));
             }
             BuiltinCallable::PartialEq => {
                 let lhs = environment.get_arg_by_index(0);
@@ -730,17 +1139,17 @@ impl Interpreter {
                     if let ValueCore::Variant(id2, index2, items2) = &rhs.core {
                         assert_eq!(id1, id2);
                         if index1 != index2 {
-                            return Interpreter::get_bool_value(false);
+                            return Ok(self.get_bool_value(false));
                         } else {
                             for (item1, item2) in items1.iter().zip(items2.iter()) {
                                 let value =
-                                    Interpreter::call_op_partial_eq(item1.clone(), item2.clone());
+                                    self.call_op_partial_eq(item1.clone(), item2.clone())?;
                                 let v = value.core.as_bool();
                                 if !v {
-                                    return Interpreter::get_bool_value(false);
+                                    return Ok(self.get_bool_value(false));
                                 }
                             }
-                            return Interpreter::get_bool_value(true);
+                            return Ok(self.get_bool_value(true));
                         }
                     }
                 }
@@ -748,14 +1157,13 @@ impl Interpreter {
                     if let ValueCore::Record(id2, items2) = &rhs.core {
                         assert_eq!(id1, id2);
                         for (item1, item2) in items1.iter().zip(items2.iter()) {
-                            let value =
-                                Interpreter::call_op_partial_eq(item1.clone(), item2.clone());
+                            let value = self.call_op_partial_eq(item1.clone(), item2.clone())?;
                             let v = value.core.as_bool();
                             if !v {
-                                return Interpreter::get_bool_value(false);
+                                return Ok(self.get_bool_value(false));
                             }
                         }
-                        return Interpreter::get_bool_value(true);
+                        return Ok(self.get_bool_value(true));
                     }
                 }
                 unimplemented!()
@@ -767,11 +1175,11 @@ impl Interpreter {
                     if let ValueCore::Variant(id2, index2, items2) = &rhs.core {
                         assert_eq!(id1, id2);
                         if index1 < index2 {
-                            return get_opt_ordering_value(Some(Ordering::Less));
+                            return Ok(get_opt_ordering_value(self, Some(Ordering::Less)));
                         } else if index1 == index2 {
                             for (item1, item2) in items1.iter().zip(items2.iter()) {
                                 let value =
-                                    Interpreter::call_op_partial_cmp(item1.clone(), item2.clone());
+                                    self.call_op_partial_cmp(item1.clone(), item2.clone())?;
                                 let some_index = self
                                     .program
                                     .get_adt_by_name(OPTION_MODULE_NAME, OPTION_TYPE_NAME)
@@ -792,11 +1200,11 @@ impl Interpreter {
                                         }
                                     }
                                 }
-                                return value;
+                                return Ok(value);
                             }
-                            return get_opt_ordering_value(Some(Ordering::Equal));
+                            return Ok(get_opt_ordering_value(self, Some(Ordering::Equal)));
                         } else {
-                            return get_opt_ordering_value(Some(Ordering::Greater));
+                            return Ok(get_opt_ordering_value(self, Some(Ordering::Greater)));
                         }
                     }
                 }
@@ -804,8 +1212,7 @@ impl Interpreter {
                     if let ValueCore::Record(id2, items2) = &rhs.core {
                         assert_eq!(id1, id2);
                         for (item1, item2) in items1.iter().zip(items2.iter()) {
-                            let value =
-                                Interpreter::call_op_partial_cmp(item1.clone(), item2.clone());
+                            let value = self.call_op_partial_cmp(item1.clone(), item2.clone())?;
                             let some_index = self
                                 .program
                                 .get_adt_by_name(OPTION_MODULE_NAME, OPTION_TYPE_NAME)
@@ -824,9 +1231,9 @@ impl Interpreter {
                                     }
                                 }
                             }
-                            return value;
+                            return Ok(value);
                         }
-                        return get_opt_ordering_value(Some(Ordering::Equal));
+                        return Ok(get_opt_ordering_value(self, Some(Ordering::Equal)));
                     }
                 }
                 unimplemented!()
@@ -838,10 +1245,10 @@ impl Interpreter {
                     if let ValueCore::Variant(id2, index2, items2) = &rhs.core {
                         assert_eq!(id1, id2);
                         if index1 < index2 {
-                            return get_ordering_value(Ordering::Less);
+                            return Ok(get_ordering_value(self, Ordering::Less));
                         } else if index1 == index2 {
                             for (item1, item2) in items1.iter().zip(items2.iter()) {
-                                let value = Interpreter::call_op_cmp(item1.clone(), item2.clone());
+                                let value = self.call_op_cmp(item1.clone(), item2.clone())?;
                                 let equal_index = self
                                     .program
                                     .get_adt_by_name(ORDERING_MODULE_NAME, ORDERING_TYPE_NAME)
@@ -851,11 +1258,11 @@ impl Interpreter {
                                         continue;
                                     }
                                 }
-                                return value;
+                                return Ok(value);
                             }
-                            return get_ordering_value(Ordering::Equal);
+                            return Ok(get_ordering_value(self, Ordering::Equal));
                         } else {
-                            return get_ordering_value(Ordering::Greater);
+                            return Ok(get_ordering_value(self, Ordering::Greater));
                         }
                     }
                 }
@@ -863,7 +1270,7 @@ impl Interpreter {
                     if let ValueCore::Record(id2, items2) = &rhs.core {
                         assert_eq!(id1, id2);
                         for (item1, item2) in items1.iter().zip(items2.iter()) {
-                            let value = Interpreter::call_op_cmp(item1.clone(), item2.clone());
+                            let value = self.call_op_cmp(item1.clone(), item2.clone())?;
                             let equal_index = self
                                 .program
                                 .get_adt_by_name(ORDERING_MODULE_NAME, ORDERING_TYPE_NAME)
@@ -873,9 +1280,9 @@ impl Interpreter {
                                     continue;
                                 }
                             }
-                            return value;
+                            return Ok(value);
                         }
-                        return get_ordering_value(Ordering::Equal);
+                        return Ok(get_ordering_value(self, Ordering::Equal));
                     }
                 }
                 unimplemented!()
@@ -890,27 +1297,28 @@ impl Interpreter {
         current_expr: Option<ExprId>,
         unifier: &Unifier,
         expr_ty: Type,
-    ) -> Value {
+    ) -> Result<EvalResult, RuntimeError> {
         assert!(expr_ty.is_concrete_type());
         let function = self.program.functions.get(&id);
         match &function.info {
             FunctionInfo::NamedFunction(info) => match info.body {
                 Some(body) => {
-                    return self.eval_expr(body, environment, unifier);
+                    return self.eval_tail_expr(body, environment, unifier);
                 }
                 None => {
-                    return self.call_extern(
+                    let value = self.call_extern(
                         &info.module,
                         &info.name,
                         environment,
                         current_expr,
                         &info.kind,
                         expr_ty,
-                    );
+                    )?;
+                    return Ok(EvalResult::Value(value));
                 }
             },
             FunctionInfo::Lambda(info) => {
-                return self.eval_expr(info.body, environment, unifier);
+                return self.eval_tail_expr(info.body, environment, unifier);
             }
             FunctionInfo::VariantConstructor(info) => {
                 let adt = self.program.typedefs.get(&info.type_id).get_adt();
@@ -920,10 +1328,10 @@ impl Interpreter {
                     let v = environment.get_arg_by_index(index);
                     values.push(v);
                 }
-                return Value::new(
+                return Ok(EvalResult::Value(Value::new(
                     ValueCore::Variant(info.type_id, info.index, values),
                     expr_ty,
-                );
+                )));
             }
             FunctionInfo::RecordConstructor(info) => {
                 let record = self.program.typedefs.get(&info.type_id).get_record();
@@ -932,7 +1340,10 @@ impl Interpreter {
                     let v = environment.get_arg_by_index(index);
                     values.push(v);
                 }
-                return Value::new(ValueCore::Record(info.type_id, values), expr_ty);
+                return Ok(EvalResult::Value(Value::new(
+                    ValueCore::Record(info.type_id, values),
+                    expr_ty,
+                )));
             }
         }
     }
@@ -940,82 +1351,165 @@ impl Interpreter {
     fn build_typedefid_cache(&mut self) {
         let option = self.program.get_adt_by_name(OPTION_MODULE_NAME, OPTION_TYPE_NAME);
         let ordering = self.program.get_adt_by_name(ORDERING_MODULE_NAME, ORDERING_TYPE_NAME);
+        let result = self.program.get_adt_by_name(RESULT_MODULE_NAME, RESULT_TYPE_NAME);
+        let io_error = self.program.get_adt_by_name(IO_FILE_MODULE_NAME, IO_ERROR_TYPE_NAME);
         let cache = TypeDefIdCache {
             option_id: option.id,
             ordering_id: ordering.id,
+            result_id: result.id,
+            io_error_id: io_error.id,
             option_variants: VariantCache::new(option),
             ordering_variants: VariantCache::new(ordering),
+            result_variants: VariantCache::new(result),
+            io_error_variants: VariantCache::new(io_error),
         };
         self.typedefid_cache = Some(cache);
     }
 
-    pub fn get_typedef_id_cache() -> TypeDefIdCache {
-        INTERPRETER_CONTEXT.with(|i| {
-            let i = i.borrow();
-            i.as_ref()
-                .expect("Interpreter not set")
-                .typedefid_cache
-                .clone()
-                .expect("TypedefId cache not set")
-        })
+    pub fn is_wrapping_arithmetic(&self) -> bool {
+        self.wrapping_arithmetic
+    }
+
+    pub fn get_program_args(&self) -> Vec<String> {
+        self.program_args.clone()
+    }
+
+    pub fn get_typedef_id_cache(&self) -> &TypeDefIdCache {
+        self.typedefid_cache
+            .as_ref()
+            .expect("TypedefId cache not set")
+    }
+
+    /// Wraps the value so that it can be stored in a Map or a Set.
+    pub fn create_key(&self, value: Value) -> Key {
+        Key::new(value, self.self_ref.clone())
     }
 
-    fn execute_main(interpreter: &Interpreter) -> Value {
+    fn execute_function(
+        interpreter: &Interpreter,
+        module: &str,
+        name: &str,
+    ) -> Result<Value, RuntimeError> {
         for (id, function) in &interpreter.program.functions.items {
             match &function.info {
                 FunctionInfo::NamedFunction(info) => {
-                    if info.module == MAIN_MODULE && info.name == MAIN_FUNCTION {
+                    if info.module == module && info.name == name {
                         let mut environment =
                             Environment::new(CallableKind::FunctionId(*id), vec![], 0);
                         let unifier = interpreter.program.get_unifier();
-                        return interpreter.execute(
+                        let function_type = interpreter
+                            .program
+                            .function_types
+                            .get(id)
+                            .expect("untyped func")
+                            .clone();
+                        interpreter.push_frame(*id, None)?;
+                        let result = interpreter.execute(
                             *id,
                             &mut environment,
                             None,
                             &unifier,
-                            Type::Tuple(vec![]),
+                            function_type,
                         );
+                        interpreter.pop_frame();
+                        return interpreter.finish_tail_call(result?);
                     }
                 }
                 _ => {}
             }
         }
 
-        panic!(
-            "Cannot find function {} in module {}",
-            MAIN_FUNCTION, MAIN_MODULE
+        panic!("Cannot find function {} in module {}", name, module);
+    }
+
+    /// The extern functions implemented by the standard library.
+    pub fn create_extern_registry() -> ExternRegistry {
+        let mut registry = ExternRegistry::new();
+        data_int::register_extern_functions(&mut registry);
+        data_float::register_extern_functions(&mut registry);
+        data_string::register_extern_functions(&mut registry);
+        data_char::register_extern_functions(&mut registry);
+        data_map::register_extern_functions(&mut registry);
+        data_set::register_extern_functions(&mut registry);
+        data_list::register_extern_functions(&mut registry);
+        std_util_basic::register_extern_functions(&mut registry);
+        std_util::register_extern_functions(&mut registry);
+        std_ops::register_extern_functions(&mut registry);
+        std_actor::register_extern_functions(&mut registry);
+        std_env::register_extern_functions(&mut registry);
+        std_io_file::register_extern_functions(&mut registry);
+        registry
+    }
+
+    /// Creates an interpreter whose functions can be called repeatedly.
+    pub fn create(
+        program: Program,
+        max_call_depth: usize,
+        wrapping_arithmetic: bool,
+        program_args: Vec<String>,
+        extern_registry: ExternRegistry,
+    ) -> Rc<Interpreter> {
+        Rc::new_cyclic(|self_ref| {
+            let mut interpreter = Interpreter::new(
+                program,
+                max_call_depth,
+                wrapping_arithmetic,
+                program_args,
+                extern_registry,
+                self_ref.clone(),
+            );
+            interpreter.build_typedefid_cache();
+            interpreter
+        })
+    }
+
+    pub fn get_program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Calls the given function with all of its arguments.
+    pub fn call_function(&self, id: FunctionId, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        let function_type = self
+            .program
+            .function_types
+            .get(&id)
+            .expect("untyped func")
+            .clone();
+        let callable = Value::new(
+            ValueCore::Callable(Callable {
+                kind: CallableKind::FunctionId(id),
+                values: vec![],
+                unifier: self.program.get_unifier(),
+            }),
+            function_type,
         );
+        let value = self.call(callable, args, None)?;
+        self.run_actors()?;
+        Ok(value)
     }
 
-    pub fn add_extern_function(
-        &mut self,
+    /// Runs the given function without arguments, then the spawned actors.
+    pub fn run_function(&self, module: &str, name: &str) -> Result<Value, RuntimeError> {
+        let value = Interpreter::execute_function(self, module, name)?;
+        self.run_actors()?;
+        Ok(value)
+    }
+
+    pub fn run(
+        program: Program,
         module: &str,
         name: &str,
-        extern_function: Box<dyn ExternFunction>,
-    ) {
-        self.extern_functions
-            .insert((module.to_string(), name.to_string()), extern_function);
-    }
-
-    pub fn run(program: Program, error_context: ErrorContext) -> Value {
-        let mut interpreter = Interpreter::new(program, error_context);
-        data_int::register_extern_functions(&mut interpreter);
-        data_float::register_extern_functions(&mut interpreter);
-        data_string::register_extern_functions(&mut interpreter);
-        data_map::register_extern_functions(&mut interpreter);
-        data_list::register_extern_functions(&mut interpreter);
-        std_util_basic::register_extern_functions(&mut interpreter);
-        std_util::register_extern_functions(&mut interpreter);
-        std_ops::register_extern_functions(&mut interpreter);
-        interpreter.build_typedefid_cache();
-        INTERPRETER_CONTEXT.with(|c| {
-            let mut p = c.borrow_mut();
-            *p = Some(interpreter);
-        });
-        INTERPRETER_CONTEXT.with(|c| {
-            let p = c.borrow();
-            let i = p.as_ref().expect("Interpreter not set");
-            Interpreter::execute_main(i)
-        })
+        max_call_depth: usize,
+        wrapping_arithmetic: bool,
+        program_args: Vec<String>,
+    ) -> Result<Value, RuntimeError> {
+        let interpreter = Interpreter::create(
+            program,
+            max_call_depth,
+            wrapping_arithmetic,
+            program_args,
+            Interpreter::create_extern_registry(),
+        );
+        interpreter.run_function(module, name)
     }
 }
@@ -1,11 +1,19 @@
+pub mod actor_runtime;
+pub mod convert;
+pub mod data_char;
 pub mod data_float;
 pub mod data_int;
 pub mod data_list;
 pub mod data_map;
+pub mod data_set;
 pub mod data_string;
 pub mod environment;
+pub mod error;
 pub mod extern_function;
 pub mod interpreter;
+pub mod std_actor;
+pub mod std_env;
+pub mod std_io_file;
 pub mod std_ops;
 pub mod std_util;
 pub mod std_util_basic;
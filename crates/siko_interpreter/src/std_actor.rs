@@ -0,0 +1,128 @@
+use crate::environment::Environment;
+use crate::error::RuntimeError;
+use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
+use crate::interpreter::Interpreter;
+use crate::util::get_opt_ordering_value;
+use crate::util::get_ordering_value;
+use crate::value::Value;
+use crate::value::ValueCore;
+use siko_constants::ACTOR_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+
+pub struct Spawn {}
+
+impl ExternFunction for Spawn {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let actor = environment.get_arg_by_index(0);
+        let current_expr = current_expr.expect("No current expr");
+        let pid = interpreter.spawn_actor(actor, current_expr)?;
+        return Ok(Value::new(ValueCore::Int(pid as i64), ty));
+    }
+}
+
+pub struct Send {}
+
+impl ExternFunction for Send {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let pid = environment.get_arg_by_index(0).core.as_int();
+        let message = environment.get_arg_by_index(1);
+        let current_expr = current_expr.expect("No current expr");
+        interpreter.send_message(pid, message, current_expr)?;
+        return Ok(Value::new(ValueCore::Tuple(vec![]), ty));
+    }
+}
+
+pub struct PidPartialEq {}
+
+impl ExternFunction for PidPartialEq {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        return Ok(interpreter.get_bool_value(l == r));
+    }
+}
+
+pub struct PidPartialOrd {}
+
+impl ExternFunction for PidPartialOrd {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        let ord = l.partial_cmp(&r);
+        return Ok(get_opt_ordering_value(interpreter, ord));
+    }
+}
+
+pub struct PidOrd {}
+
+impl ExternFunction for PidOrd {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        let ord = l.cmp(&r);
+        return Ok(get_ordering_value(interpreter, ord));
+    }
+}
+
+pub struct PidShow {}
+
+impl ExternFunction for PidShow {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let pid = environment.get_arg_by_index(0).core.as_int();
+        return Ok(Value::new(ValueCore::String(format!("Pid {}", pid)), ty));
+    }
+}
+
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(ACTOR_MODULE_NAME, "spawn", Box::new(Spawn {}));
+    registry.add_extern_function(ACTOR_MODULE_NAME, "send", Box::new(Send {}));
+    registry.add_extern_function(ACTOR_MODULE_NAME, "opEq", Box::new(PidPartialEq {}));
+    registry.add_extern_function(ACTOR_MODULE_NAME, "partialCmp", Box::new(PidPartialOrd {}));
+    registry.add_extern_function(ACTOR_MODULE_NAME, "cmp", Box::new(PidOrd {}));
+    registry.add_extern_function(ACTOR_MODULE_NAME, "show", Box::new(PidShow {}));
+}
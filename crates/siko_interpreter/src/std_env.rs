@@ -0,0 +1,65 @@
+use crate::environment::Environment;
+use crate::error::RuntimeError;
+use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
+use crate::interpreter::Interpreter;
+use crate::util::create_none;
+use crate::util::create_some;
+use crate::value::Value;
+use crate::value::ValueCore;
+use siko_constants::ENV_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+use std::env;
+
+pub struct Args {}
+
+impl ExternFunction for Args {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let string_ty = interpreter.get_string_type();
+        let args = interpreter
+            .get_program_args()
+            .into_iter()
+            .map(|arg| Value::new(ValueCore::String(arg), string_ty.clone()))
+            .collect();
+        return Ok(Value::new(ValueCore::List(args), ty));
+    }
+}
+
+pub struct GetEnv {}
+
+impl ExternFunction for GetEnv {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let name = environment.get_arg_by_index(0).core.as_string();
+        let string_ty = ty.get_type_args().remove(0);
+        match env::var(&name) {
+            Ok(value) => {
+                return Ok(create_some(
+                    interpreter,
+                    Value::new(ValueCore::String(value), string_ty),
+                ))
+            }
+            Err(_) => return Ok(create_none(interpreter, string_ty)),
+        }
+    }
+}
+
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(ENV_MODULE_NAME, "args", Box::new(Args {}));
+    registry.add_extern_function(ENV_MODULE_NAME, "getEnv", Box::new(GetEnv {}));
+}
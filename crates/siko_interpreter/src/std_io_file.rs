@@ -0,0 +1,271 @@
+use crate::environment::Environment;
+use crate::error::RuntimeError;
+use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
+use crate::interpreter::Interpreter;
+use crate::util::create_err;
+use crate::util::create_ok;
+use crate::value::Value;
+use crate::value::ValueCore;
+use siko_constants::IO_ERROR_TYPE_NAME;
+use siko_constants::IO_FILE_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+use std::env;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::process;
+
+fn create_io_error(
+    interpreter: &Interpreter,
+    path: &str,
+    error: io::Error,
+    result_ty: Type,
+) -> Value {
+    let cache = interpreter.get_typedef_id_cache();
+    let variant = match error.kind() {
+        io::ErrorKind::NotFound => "NotFound",
+        io::ErrorKind::PermissionDenied => "PermissionDenied",
+        io::ErrorKind::AlreadyExists => "AlreadyExists",
+        io::ErrorKind::InvalidData => "InvalidData",
+        _ => "Other",
+    };
+    let message = Value::new(
+        ValueCore::String(format!("{}: {}", path, error)),
+        interpreter.get_string_type(),
+    );
+    let io_error_ty = Type::Named(IO_ERROR_TYPE_NAME.to_string(), cache.io_error_id, vec![]);
+    let core = ValueCore::Variant(
+        cache.io_error_id,
+        cache.io_error_variants.get_index(variant),
+        vec![message],
+    );
+    return create_err(interpreter, Value::new(core, io_error_ty), result_ty);
+}
+
+fn create_io_result(
+    interpreter: &Interpreter,
+    path: &str,
+    result: io::Result<Value>,
+    result_ty: Type,
+) -> Value {
+    match result {
+        Ok(value) => create_ok(interpreter, value, result_ty),
+        Err(error) => create_io_error(interpreter, path, error, result_ty),
+    }
+}
+
+fn create_unit() -> Value {
+    return Value::new(ValueCore::Tuple(vec![]), Type::Tuple(vec![]));
+}
+
+pub struct ReadFile {}
+
+impl ExternFunction for ReadFile {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        let result = fs::read_to_string(&path)
+            .map(|content| Value::new(ValueCore::String(content), interpreter.get_string_type()));
+        return Ok(create_io_result(interpreter, &path, result, ty));
+    }
+}
+
+pub struct WriteFile {}
+
+impl ExternFunction for WriteFile {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        let content = environment.get_arg_by_index(1).core.as_string();
+        let result = fs::write(&path, content).map(|_| create_unit());
+        return Ok(create_io_result(interpreter, &path, result, ty));
+    }
+}
+
+pub struct AppendFile {}
+
+impl ExternFunction for AppendFile {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        let content = environment.get_arg_by_index(1).core.as_string();
+        let result = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(content.as_bytes()))
+            .map(|_| create_unit());
+        return Ok(create_io_result(interpreter, &path, result, ty));
+    }
+}
+
+pub struct FileExists {}
+
+impl ExternFunction for FileExists {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Result<Value, RuntimeError> {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        return Ok(interpreter.get_bool_value(Path::new(&path).exists()));
+    }
+}
+
+pub struct ListDir {}
+
+impl ExternFunction for ListDir {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        let list_ty = ty.get_type_args().remove(0);
+        let result = fs::read_dir(&path).and_then(|entries| {
+            let mut names = Vec::new();
+            for entry in entries {
+                let entry = entry?;
+                names.push(entry.file_name().to_string_lossy().to_string());
+            }
+            names.sort();
+            let string_ty = interpreter.get_string_type();
+            let items = names
+                .into_iter()
+                .map(|name| Value::new(ValueCore::String(name), string_ty.clone()))
+                .collect();
+            Ok(Value::new(ValueCore::List(items), list_ty))
+        });
+        return Ok(create_io_result(interpreter, &path, result, ty));
+    }
+}
+
+pub struct CreateDir {}
+
+impl ExternFunction for CreateDir {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        let result = fs::create_dir_all(&path).map(|_| create_unit());
+        return Ok(create_io_result(interpreter, &path, result, ty));
+    }
+}
+
+pub struct RemoveFile {}
+
+impl ExternFunction for RemoveFile {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        let result = fs::remove_file(&path).map(|_| create_unit());
+        return Ok(create_io_result(interpreter, &path, result, ty));
+    }
+}
+
+pub struct RemoveDir {}
+
+impl ExternFunction for RemoveDir {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        let result = fs::remove_dir(&path).map(|_| create_unit());
+        return Ok(create_io_result(interpreter, &path, result, ty));
+    }
+}
+
+pub struct CreateTempDir {}
+
+impl ExternFunction for CreateTempDir {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let prefix = environment.get_arg_by_index(0).core.as_string();
+        let temp_dir = env::temp_dir();
+        let mut index = 0;
+        // the process id keeps concurrent runs apart, the index skips leftovers of earlier runs
+        let result = loop {
+            let path = temp_dir.join(format!("{}_{}_{}", prefix, process::id(), index));
+            match fs::create_dir(&path) {
+                Ok(_) => {
+                    let path = path.to_string_lossy().to_string();
+                    break Ok(Value::new(
+                        ValueCore::String(path),
+                        interpreter.get_string_type(),
+                    ));
+                }
+                Err(ref error) if error.kind() == io::ErrorKind::AlreadyExists => index += 1,
+                Err(error) => break Err(error),
+            }
+        };
+        let path = temp_dir.to_string_lossy().to_string();
+        return Ok(create_io_result(interpreter, &path, result, ty));
+    }
+}
+
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function(IO_FILE_MODULE_NAME, "readFile", Box::new(ReadFile {}));
+    registry.add_extern_function(IO_FILE_MODULE_NAME, "writeFile", Box::new(WriteFile {}));
+    registry.add_extern_function(IO_FILE_MODULE_NAME, "appendFile", Box::new(AppendFile {}));
+    registry.add_extern_function(IO_FILE_MODULE_NAME, "fileExists", Box::new(FileExists {}));
+    registry.add_extern_function(IO_FILE_MODULE_NAME, "listDir", Box::new(ListDir {}));
+    registry.add_extern_function(IO_FILE_MODULE_NAME, "createDir", Box::new(CreateDir {}));
+    registry.add_extern_function(IO_FILE_MODULE_NAME, "removeFile", Box::new(RemoveFile {}));
+    registry.add_extern_function(IO_FILE_MODULE_NAME, "removeDir", Box::new(RemoveDir {}));
+    registry.add_extern_function(
+        IO_FILE_MODULE_NAME,
+        "createTempDir",
+        Box::new(CreateTempDir {}),
+    );
+}
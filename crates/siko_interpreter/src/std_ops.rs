@@ -1,7 +1,11 @@
 use crate::environment::Environment;
+use crate::error::RuntimeError;
 use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
 use crate::interpreter::Interpreter;
 use crate::value::Value;
+use crate::value::ValueCore;
+use im_rc::Vector;
 use siko_ir::expr::ExprId;
 use siko_ir::function::NamedFunctionKind;
 use siko_ir::types::Type;
@@ -11,14 +15,15 @@ pub struct And {}
 impl ExternFunction for And {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_bool();
         let r = environment.get_arg_by_index(1).core.as_bool();
-        return Interpreter::get_bool_value(l && r);
+        return Ok(interpreter.get_bool_value(l && r));
     }
 }
 
@@ -27,22 +32,113 @@ pub struct Or {}
 impl ExternFunction for Or {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         _: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let l = environment.get_arg_by_index(0).core.as_bool();
         if l {
-            return Interpreter::get_bool_value(l);
+            return Ok(interpreter.get_bool_value(l));
         } else {
             let r = environment.get_arg_by_index(1).core.as_bool();
-            return Interpreter::get_bool_value(r);
+            return Ok(interpreter.get_bool_value(r));
         }
     }
 }
 
-pub fn register_extern_functions(interpreter: &mut Interpreter) {
-    interpreter.add_extern_function("Std.Ops", "opAnd", Box::new(And {}));
-    interpreter.add_extern_function("Std.Ops", "opOr", Box::new(Or {}));
+fn to_int(interpreter: &Interpreter, value: Value) -> Result<i64, RuntimeError> {
+    if let ValueCore::Int(v) = value.core {
+        return Ok(v);
+    }
+    let int_ty = interpreter.get_int_type();
+    let v = interpreter.call_specific_class_member(vec![value], "Enum", "toInt", int_ty)?;
+    return Ok(v.core.as_int());
+}
+
+fn from_int(
+    interpreter: &Interpreter,
+    v: i64,
+    is_int: bool,
+    ty: &Type,
+) -> Result<Value, RuntimeError> {
+    let int_value = Value::new(ValueCore::Int(v), interpreter.get_int_type());
+    if is_int {
+        return Ok(int_value);
+    }
+    interpreter.call_specific_class_member(vec![int_value], "Enum", "fromInt", ty.clone())
+}
+
+fn create_range(
+    interpreter: &Interpreter,
+    from: Value,
+    step: i64,
+    to: Value,
+    ty: Type,
+    current_expr: Option<ExprId>,
+) -> Result<Value, RuntimeError> {
+    if step == 0 {
+        return Err(interpreter.runtime_error(format!("Range step cannot be zero"), current_expr));
+    }
+    let item_ty = ty.get_type_args()[0].clone();
+    let is_int = match from.core {
+        ValueCore::Int(_) => true,
+        _ => false,
+    };
+    let from = to_int(interpreter, from)?;
+    let to = to_int(interpreter, to)?;
+    let mut items = Vec::new();
+    let mut current = from;
+    while (step > 0 && current <= to) || (step < 0 && current >= to) {
+        items.push(from_int(interpreter, current, is_int, &item_ty)?);
+        current = match current.checked_add(step) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    return Ok(Value::new(ValueCore::List(Vector::from(items)), ty));
+}
+
+pub struct EnumFromTo {}
+
+impl ExternFunction for EnumFromTo {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let from = environment.get_arg_by_index(0);
+        let to = environment.get_arg_by_index(1);
+        return create_range(interpreter, from, 1, to, ty, current_expr);
+    }
+}
+
+pub struct EnumFromThenTo {}
+
+impl ExternFunction for EnumFromThenTo {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let from = environment.get_arg_by_index(0);
+        let next = environment.get_arg_by_index(1);
+        let to = environment.get_arg_by_index(2);
+        let step = to_int(interpreter, next)? - to_int(interpreter, from.clone())?;
+        return create_range(interpreter, from, step, to, ty, current_expr);
+    }
+}
+
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function("Std.Ops", "opAnd", Box::new(And {}));
+    registry.add_extern_function("Std.Ops", "opOr", Box::new(Or {}));
+    registry.add_extern_function("Std.Ops", "enumFromTo", Box::new(EnumFromTo {}));
+    registry.add_extern_function("Std.Ops", "enumFromThenTo", Box::new(EnumFromThenTo {}));
 }
@@ -1,5 +1,7 @@
 use crate::environment::Environment;
+use crate::error::RuntimeError;
 use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
 use crate::interpreter::Interpreter;
 use crate::value::Value;
 use crate::value::ValueCore;
@@ -12,19 +14,20 @@ pub struct Assert {}
 impl ExternFunction for Assert {
     fn call(
         &self,
+        interpreter: &Interpreter,
         environment: &mut Environment,
         current_expr: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let v = environment.get_arg_by_index(0).core.as_bool();
         if !v {
-            Interpreter::call_abort(current_expr.expect("No current expr"));
+            return Err(interpreter.runtime_error(format!("Assertion failed"), current_expr));
         }
-        return Value::new(ValueCore::Tuple(vec![]), ty);
+        return Ok(Value::new(ValueCore::Tuple(vec![]), ty));
     }
 }
 
-pub fn register_extern_functions(interpreter: &mut Interpreter) {
-    interpreter.add_extern_function("Std.Util", "assert", Box::new(Assert {}));
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function("Std.Util", "assert", Box::new(Assert {}));
 }
@@ -1,25 +1,34 @@
 use crate::environment::Environment;
+use crate::error::RuntimeError;
 use crate::extern_function::ExternFunction;
+use crate::extern_function::ExternRegistry;
 use crate::interpreter::Interpreter;
+use crate::util::create_none;
+use crate::util::create_some;
 use crate::value::Value;
 use crate::value::ValueCore;
 use siko_ir::expr::ExprId;
 use siko_ir::function::NamedFunctionKind;
 use siko_ir::types::Type;
+use std::io;
+use std::io::BufRead;
+use std::io::Read;
+use std::io::Write;
 
 pub struct Print {}
 
 impl ExternFunction for Print {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let v = environment.get_arg_by_index(0).core.as_string();
         print!("{}", v);
-        return Value::new(ValueCore::Tuple(vec![]), ty);
+        return Ok(Value::new(ValueCore::Tuple(vec![]), ty));
     }
 }
 
@@ -28,18 +37,133 @@ pub struct PrintLn {}
 impl ExternFunction for PrintLn {
     fn call(
         &self,
+        _: &Interpreter,
         environment: &mut Environment,
         _: Option<ExprId>,
         _: &NamedFunctionKind,
         ty: Type,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         let v = environment.get_arg_by_index(0).core.as_string();
         println!("{}", v);
-        return Value::new(ValueCore::Tuple(vec![]), ty);
+        return Ok(Value::new(ValueCore::Tuple(vec![]), ty));
     }
 }
 
-pub fn register_extern_functions(interpreter: &mut Interpreter) {
-    interpreter.add_extern_function("Std.Util.Basic", "print", Box::new(Print {}));
-    interpreter.add_extern_function("Std.Util.Basic", "println", Box::new(PrintLn {}));
+pub struct EPrint {}
+
+impl ExternFunction for EPrint {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let v = environment.get_arg_by_index(0).core.as_string();
+        eprint!("{}", v);
+        return Ok(Value::new(ValueCore::Tuple(vec![]), ty));
+    }
+}
+
+pub struct EPrintLn {}
+
+impl ExternFunction for EPrintLn {
+    fn call(
+        &self,
+        _: &Interpreter,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let v = environment.get_arg_by_index(0).core.as_string();
+        eprintln!("{}", v);
+        return Ok(Value::new(ValueCore::Tuple(vec![]), ty));
+    }
+}
+
+pub struct Flush {}
+
+impl ExternFunction for Flush {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        _: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        if let Err(e) = io::stdout().flush() {
+            return Err(interpreter.runtime_error(format!("Flush failed: {}", e), current_expr));
+        }
+        return Ok(Value::new(ValueCore::Tuple(vec![]), ty));
+    }
+}
+
+pub struct ReadLine {}
+
+impl ExternFunction for ReadLine {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        _: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let string_ty = ty.get_type_args().remove(0);
+        let mut line = String::new();
+        match io::stdin().lock().read_line(&mut line) {
+            Ok(0) => return Ok(create_none(interpreter, string_ty)),
+            Ok(_) => {
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                return Ok(create_some(
+                    interpreter,
+                    Value::new(ValueCore::String(line), string_ty),
+                ));
+            }
+            Err(e) => {
+                return Err(
+                    interpreter.runtime_error(format!("Reading stdin failed: {}", e), current_expr)
+                )
+            }
+        }
+    }
+}
+
+pub struct ReadAll {}
+
+impl ExternFunction for ReadAll {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        _: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let mut content = String::new();
+        if let Err(e) = io::stdin().read_to_string(&mut content) {
+            return Err(
+                interpreter.runtime_error(format!("Reading stdin failed: {}", e), current_expr)
+            );
+        }
+        return Ok(Value::new(ValueCore::String(content), ty));
+    }
+}
+
+pub fn register_extern_functions(registry: &mut ExternRegistry) {
+    registry.add_extern_function("Std.Util.Basic", "print", Box::new(Print {}));
+    registry.add_extern_function("Std.Util.Basic", "println", Box::new(PrintLn {}));
+    registry.add_extern_function("Std.Util.Basic", "eprint", Box::new(EPrint {}));
+    registry.add_extern_function("Std.Util.Basic", "eprintln", Box::new(EPrintLn {}));
+    registry.add_extern_function("Std.Util.Basic", "flush", Box::new(Flush {}));
+    registry.add_extern_function("Std.Util.Basic", "readLine", Box::new(ReadLine {}));
+    registry.add_extern_function("Std.Util.Basic", "readAll", Box::new(ReadAll {}));
 }
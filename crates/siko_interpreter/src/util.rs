@@ -15,21 +15,21 @@ pub fn get_instance_name_from_kind(kind: &NamedFunctionKind) -> &str {
     }
 }
 
-pub fn get_opt_ordering_value(ordering: Option<Ordering>) -> Value {
+pub fn get_opt_ordering_value(interpreter: &Interpreter, ordering: Option<Ordering>) -> Value {
     match ordering {
         Some(ordering) => {
-            let value = get_ordering_value(ordering);
-            return create_some(value);
+            let value = get_ordering_value(interpreter, ordering);
+            return create_some(interpreter, value);
         }
         None => {
-            let value = create_ordering(0);
-            return create_none(value.ty);
+            let value = create_ordering(interpreter, 0);
+            return create_none(interpreter, value.ty);
         }
     }
 }
 
-pub fn create_some(value: Value) -> Value {
-    let cache = Interpreter::get_typedef_id_cache();
+pub fn create_some(interpreter: &Interpreter, value: Value) -> Value {
+    let cache = interpreter.get_typedef_id_cache();
     let concrete_type = Type::Named(
         OPTION_TYPE_NAME.to_string(),
         cache.option_id,
@@ -44,8 +44,8 @@ pub fn create_some(value: Value) -> Value {
     some_value
 }
 
-pub fn create_none(value_ty: Type) -> Value {
-    let cache = Interpreter::get_typedef_id_cache();
+pub fn create_none(interpreter: &Interpreter, value_ty: Type) -> Value {
+    let cache = interpreter.get_typedef_id_cache();
     let concrete_type = Type::Named(OPTION_TYPE_NAME.to_string(), cache.option_id, vec![value_ty]);
     let core = ValueCore::Variant(
         cache.option_id,
@@ -56,19 +56,41 @@ pub fn create_none(value_ty: Type) -> Value {
     none_value
 }
 
-pub fn create_ordering(index: usize) -> Value {
-    let cache = Interpreter::get_typedef_id_cache();
+pub fn create_ok(interpreter: &Interpreter, value: Value, result_ty: Type) -> Value {
+    let cache = interpreter.get_typedef_id_cache();
+    let core = ValueCore::Variant(
+        cache.result_id,
+        cache.result_variants.get_index("Ok"),
+        vec![value],
+    );
+    Value::new(core, result_ty)
+}
+
+pub fn create_err(interpreter: &Interpreter, value: Value, result_ty: Type) -> Value {
+    let cache = interpreter.get_typedef_id_cache();
+    let core = ValueCore::Variant(
+        cache.result_id,
+        cache.result_variants.get_index("Err"),
+        vec![value],
+    );
+    Value::new(core, result_ty)
+}
+
+pub fn create_ordering(interpreter: &Interpreter, index: usize) -> Value {
+    let cache = interpreter.get_typedef_id_cache();
     let concrete_type = Type::Named(ORDERING_TYPE_NAME.to_string(), cache.ordering_id, vec![]);
     let core = ValueCore::Variant(cache.ordering_id, index, vec![]);
     let value = Value::new(core, concrete_type);
     value
 }
 
-pub fn get_ordering_value(ordering: Ordering) -> Value {
-    let cache = Interpreter::get_typedef_id_cache();
+pub fn get_ordering_value(interpreter: &Interpreter, ordering: Ordering) -> Value {
+    let cache = interpreter.get_typedef_id_cache();
     match ordering {
-        Ordering::Less => create_ordering(cache.ordering_variants.get_index("Less")),
-        Ordering::Equal => create_ordering(cache.ordering_variants.get_index("Equal")),
-        Ordering::Greater => create_ordering(cache.ordering_variants.get_index("Greater")),
+        Ordering::Less => create_ordering(interpreter, cache.ordering_variants.get_index("Less")),
+        Ordering::Equal => create_ordering(interpreter, cache.ordering_variants.get_index("Equal")),
+        Ordering::Greater => {
+            create_ordering(interpreter, cache.ordering_variants.get_index("Greater"))
+        }
     }
 }
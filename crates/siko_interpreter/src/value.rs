@@ -1,12 +1,16 @@
+use crate::error::RuntimeError;
 use crate::interpreter::Interpreter;
+use im_rc::OrdMap;
+use im_rc::OrdSet;
+use im_rc::Vector;
 use siko_ir::data::TypeDefId;
 use siko_ir::function::FunctionId;
-use siko_ir::program::Program;
 use siko_ir::types::Type;
 use siko_ir::unifier::Unifier;
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
 use std::fmt;
+use std::rc::Rc;
+use std::rc::Weak;
 
 #[derive(Debug, Clone, Copy)]
 pub enum BuiltinCallable {
@@ -14,6 +18,8 @@ pub enum BuiltinCallable {
     PartialEq,
     PartialOrd,
     Ord,
+    ToInt,
+    FromInt,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -41,35 +47,61 @@ impl Value {
     }
 }
 
-impl PartialEq for Value {
-    fn eq(&self, other: &Self) -> bool {
-        let copy = self.clone();
-        let other = other.clone();
-        let v = Interpreter::call_op_eq(copy, other);
-        v.core.as_bool()
+/// A value stored in a Map or a Set, ordered by the Ord instance of its type.
+#[derive(Debug, Clone)]
+pub struct Key {
+    pub value: Value,
+    interpreter: Weak<Interpreter>,
+}
+
+impl Key {
+    pub fn new(value: Value, interpreter: Weak<Interpreter>) -> Key {
+        Key {
+            value: value,
+            interpreter: interpreter,
+        }
+    }
+
+    fn get_interpreter(&self) -> Rc<Interpreter> {
+        self.interpreter.upgrade().expect("Interpreter dropped")
     }
 }
 
-impl PartialOrd for Value {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let copy = self.clone();
-        let other = other.clone();
-        let v = Interpreter::call_op_partial_cmp(copy, other);
-        match v.core.as_option(0, 1) {
-            Some(v) => Some(v.core.as_ordering(0, 1, 2)),
-            None => None,
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        let interpreter = self.get_interpreter();
+        let copy = self.value.clone();
+        let other = other.value.clone();
+        match interpreter.call_op_eq(copy, other) {
+            Ok(v) => v.core.as_bool(),
+            Err(e) => {
+                interpreter.set_pending_error(e);
+                false
+            }
         }
     }
 }
 
-impl Eq for Value {}
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-impl Ord for Value {
+impl Ord for Key {
     fn cmp(&self, other: &Self) -> Ordering {
-        let copy = self.clone();
-        let other = other.clone();
-        let v = Interpreter::call_op_cmp(copy, other);
-        v.core.as_ordering(0, 1, 2)
+        let interpreter = self.get_interpreter();
+        let copy = self.value.clone();
+        let other = other.value.clone();
+        match interpreter.call_op_cmp(copy, other) {
+            Ok(v) => v.core.as_ordering(0, 1, 2),
+            Err(e) => {
+                interpreter.set_pending_error(e);
+                Ordering::Equal
+            }
+        }
     }
 }
 
@@ -78,12 +110,14 @@ pub enum ValueCore {
     Int(i64),
     Float(f64),
     String(String),
+    Char(char),
     Tuple(Vec<Value>),
     Callable(Callable),
     Variant(TypeDefId, usize, Vec<Value>),
     Record(TypeDefId, Vec<Value>),
-    List(Vec<Value>),
-    Map(BTreeMap<Value, Value>),
+    List(Vector<Value>),
+    Map(OrdMap<Key, Value>),
+    Set(OrdSet<Key>),
 }
 
 impl ValueCore {
@@ -108,6 +142,13 @@ impl ValueCore {
         }
     }
 
+    pub fn as_char(&self) -> char {
+        match self {
+            ValueCore::Char(c) => *c,
+            _ => unreachable!(),
+        }
+    }
+
     pub fn as_bool(&self) -> bool {
         match self {
             ValueCore::Variant(_, 0, _) => true,
@@ -145,34 +186,49 @@ impl ValueCore {
         }
     }
 
-    pub fn as_map(&self) -> BTreeMap<Value, Value> {
+    pub fn as_map(&self) -> OrdMap<Key, Value> {
         match self {
             ValueCore::Map(m) => m.clone(),
             _ => unreachable!(),
         }
     }
 
-    pub fn as_list(&self) -> Vec<Value> {
+    pub fn as_set(&self) -> OrdSet<Key> {
+        match self {
+            ValueCore::Set(s) => s.clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn as_list(&self) -> Vector<Value> {
         match self {
             ValueCore::List(l) => l.clone(),
             _ => unreachable!(),
         }
     }
 
-    pub fn show(&self, program: &Program) -> String {
+    pub fn as_tuple(&self) -> Vec<Value> {
+        match self {
+            ValueCore::Tuple(items) => items.clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn show(&self, interpreter: &Interpreter) -> Result<String, RuntimeError> {
+        let program = interpreter.get_program();
         match self {
             ValueCore::Variant(id, index, items) => {
                 let adt = program.typedefs.get(id).get_adt();
                 let variant = &adt.variants[*index];
                 let mut item_strings = Vec::new();
                 for item in items {
-                    let item_str = Interpreter::call_show(item.clone());
+                    let item_str = interpreter.call_show(item.clone())?;
                     item_strings.push(format!("({})", item_str));
                 }
                 if item_strings.is_empty() {
-                    format!("{}", variant.name)
+                    Ok(format!("{}", variant.name))
                 } else {
-                    format!("{} {}", variant.name, item_strings.join(" "))
+                    Ok(format!("{} {}", variant.name, item_strings.join(" ")))
                 }
             }
             ValueCore::Record(id, fields) => {
@@ -180,13 +236,17 @@ impl ValueCore {
                 let mut field_strings = Vec::new();
                 for (index, field_value) in fields.iter().enumerate() {
                     let field = &record.fields[index];
-                    let field_str = Interpreter::call_show(field_value.clone());
+                    let field_str = interpreter.call_show(field_value.clone())?;
                     field_strings.push(format!("{}: {}", field.name, field_str));
                 }
                 if field_strings.is_empty() {
-                    format!("{}", record.name)
+                    Ok(format!("{}", record.name))
                 } else {
-                    format!("{} {{ {} }}", record.name, field_strings.join(", "))
+                    Ok(format!(
+                        "{} {{ {} }}",
+                        record.name,
+                        field_strings.join(", ")
+                    ))
                 }
             }
             _ => unimplemented!(),
@@ -200,6 +260,7 @@ impl fmt::Display for ValueCore {
             ValueCore::Int(v) => write!(f, "{}", v),
             ValueCore::Float(v) => write!(f, "{}", v),
             ValueCore::String(v) => write!(f, "{}", v),
+            ValueCore::Char(v) => write!(f, "{}", v),
             ValueCore::Tuple(vs) => {
                 let ss: Vec<_> = vs.iter().map(|v| format!("{}", v.core)).collect();
                 write!(f, "({})", ss.join(", "))
@@ -220,10 +281,14 @@ impl fmt::Display for ValueCore {
             ValueCore::Map(vs) => {
                 let ss: Vec<_> = vs
                     .iter()
-                    .map(|(k, v)| format!("{}:{}", k.core, v.core))
+                    .map(|(k, v)| format!("{}:{}", k.value.core, v.core))
                     .collect();
                 write!(f, "{{{}}}", ss.join(", "))
             }
+            ValueCore::Set(vs) => {
+                let ss: Vec<_> = vs.iter().map(|v| format!("{}", v.value.core)).collect();
+                write!(f, "{{{}}}", ss.join(", "))
+            }
         }
     }
 }
@@ -0,0 +1,41 @@
+use crate::data::TypeDefId;
+use crate::function::FunctionId;
+use crate::type_signature::TypeSignatureId;
+use siko_location_info::location_id::LocationId;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct ProtocolHandler {
+    pub protocol_id: TypeDefId,
+    pub protocol_location_id: LocationId,
+    pub handler_func: FunctionId,
+    pub handler_func_location_id: LocationId,
+}
+
+#[derive(Debug, Clone)]
+pub struct Actor {
+    pub id: ActorId,
+    pub name: String,
+    pub module: String,
+    pub type_id: TypeDefId,
+    pub type_signature: TypeSignatureId,
+    pub handlers: Vec<ProtocolHandler>,
+    pub location_id: LocationId,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ActorId {
+    pub id: usize,
+}
+
+impl fmt::Display for ActorId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ActorId({})", self.id)
+    }
+}
+
+impl From<usize> for ActorId {
+    fn from(id: usize) -> ActorId {
+        ActorId { id: id }
+    }
+}
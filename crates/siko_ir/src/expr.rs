@@ -105,6 +105,7 @@ pub enum Expr {
     IntegerLiteral(i64),
     FloatLiteral(f64),
     StringLiteral(String),
+    CharLiteral(char),
     Do(Vec<ExprId>),
     Bind(PatternId, ExprId),
     ArgRef(FunctionArgumentRef),
@@ -140,6 +141,7 @@ impl fmt::Display for Expr {
             Expr::IntegerLiteral(v) => write!(f, "Integer({})", v),
             Expr::FloatLiteral(v) => write!(f, "Float({})", v),
             Expr::StringLiteral(v) => write!(f, "String({})", v),
+            Expr::CharLiteral(v) => write!(f, "Char({:?})", v),
             Expr::Do(items) => write!(f, "Do({})", format_list(items)),
             Expr::Bind(pattern_id, expr) => write!(f, "Bind({}, {})", pattern_id, expr),
             Expr::ArgRef(v) => write!(f, "{}", v),
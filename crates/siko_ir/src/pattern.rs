@@ -32,6 +32,7 @@ pub enum Pattern {
     IntegerLiteral(i64),
     FloatLiteral(f64),
     StringLiteral(String),
+    CharLiteral(char),
     Typed(PatternId, TypeSignatureId),
 }
 
@@ -1,3 +1,5 @@
+use crate::actor::Actor;
+use crate::actor::ActorId;
 use crate::class::Class;
 use crate::class::ClassId;
 use crate::class::ClassMember;
@@ -21,6 +23,8 @@ use crate::types::Type;
 use crate::unifier::Unifier;
 use siko_constants::BOOL_MODULE_NAME;
 use siko_constants::BOOL_TYPE_NAME;
+use siko_constants::CHAR_MODULE_NAME;
+use siko_constants::CHAR_TYPE_NAME;
 use siko_constants::INT_MODULE_NAME;
 use siko_constants::INT_TYPE_NAME;
 use siko_constants::FLOAT_MODULE_NAME;
@@ -49,6 +53,7 @@ pub struct Program {
     pub classes: ItemContainer<ClassId, Class>,
     pub class_members: ItemContainer<ClassMemberId, ClassMember>,
     pub instances: ItemContainer<InstanceId, Instance>,
+    pub actors: ItemContainer<ActorId, Actor>,
     pub instance_resolution_cache: Rc<RefCell<InstanceResolutionCache>>,
     pub expr_types: BTreeMap<ExprId, Type>,
     pub function_types: BTreeMap<FunctionId, Type>,
@@ -69,6 +74,7 @@ impl Program {
             classes: ItemContainer::new(),
             class_members: ItemContainer::new(),
             instances: ItemContainer::new(),
+            actors: ItemContainer::new(),
             instance_resolution_cache: Rc::new(RefCell::new(InstanceResolutionCache::new())),
             expr_types: BTreeMap::new(),
             function_types: BTreeMap::new(),
@@ -94,6 +100,11 @@ impl Program {
         Type::Named(STRING_TYPE_NAME.to_string(), id, Vec::new())
     }
 
+    pub fn get_char_type(&self) -> Type {
+        let id = self.get_named_type(CHAR_MODULE_NAME, CHAR_TYPE_NAME);
+        Type::Named(CHAR_TYPE_NAME.to_string(), id, Vec::new())
+    }
+
     pub fn get_bool_type(&self) -> Type {
         let id = self.get_named_type(BOOL_MODULE_NAME, BOOL_TYPE_NAME);
         Type::Named(BOOL_TYPE_NAME.to_string(), id, Vec::new())
@@ -43,6 +43,7 @@ pub fn walk_expr(expr_id: &ExprId, visitor: &mut dyn Visitor) {
         Expr::IntegerLiteral(_) => {}
         Expr::FloatLiteral(_) => {}
         Expr::StringLiteral(_) => {}
+        Expr::CharLiteral(_) => {}
         Expr::Do(items) => {
             for item in items {
                 walk_expr(item, visitor);
@@ -122,6 +123,7 @@ fn walk_pattern(pattern_id: &PatternId, visitor: &mut dyn Visitor) {
         Pattern::IntegerLiteral(_) => {}
         Pattern::FloatLiteral(_) => {}
         Pattern::StringLiteral(_) => {}
+        Pattern::CharLiteral(_) => {}
         Pattern::Typed(id, _) => {
             walk_pattern(id, visitor);
         }
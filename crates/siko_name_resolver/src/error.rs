@@ -47,6 +47,10 @@ pub enum ResolverError {
     NamedInstancedNotUnique(String, String, LocationId),
     PatternBindConflict(String, Vec<LocationId>),
     PatternBindNotPresent(String, LocationId),
+    UnknownProtocol(String, LocationId),
+    UnknownProtocolHandler(String, LocationId),
+    ConflictingProtocolHandlers(String, String, Vec<LocationId>),
+    FunctionClauseArgumentMismatch(String, usize, usize, LocationId),
 }
 
 #[derive(Debug)]
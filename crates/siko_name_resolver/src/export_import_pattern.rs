@@ -129,6 +129,14 @@ fn match_item(name: &str, group: bool, item: &Item, program: &Program) -> bool {
             class.name == name && !group
         }
         Item::ClassMember(_, _, _) => false,
+        Item::Protocol(id, _) => {
+            let protocol = program.protocols.get(&id);
+            protocol.name == name && !group
+        }
+        Item::Actor(id, _) => {
+            let actor = program.actors.get(&id);
+            actor.name == name && !group
+        }
     }
 }
 
@@ -27,6 +27,7 @@ use siko_ir::pattern::PatternId as IrPatternId;
 use siko_ir::program::Program as IrProgram;
 use siko_location_info::item::ItemInfo;
 use siko_location_info::location_id::LocationId;
+use siko_syntax::expr::Case;
 use siko_syntax::expr::Expr;
 use siko_syntax::expr::ExprId;
 use siko_syntax::pattern::Pattern;
@@ -82,6 +83,10 @@ fn resolve_item_path(
                 Item::ClassMember(_, _, ir_class_member_id) => {
                     return PathResolveResult::ClassMemberRef(ir_class_member_id);
                 }
+                Item::Protocol(_, ir_typedef_id) | Item::Actor(_, ir_typedef_id) => {
+                    let ir_adt = ir_program.typedefs.get(&ir_typedef_id).get_adt();
+                    return PathResolveResult::FunctionRef(ir_adt.variants[0].constructor);
+                }
                 _ => {}
             }
         }
@@ -166,6 +171,9 @@ fn resolve_pattern_type_constructor(
                         return IrPattern::Variant(ir_typedef_id, index, ids);
                     }
                 }
+                Item::Protocol(_, ir_typedef_id) | Item::Actor(_, ir_typedef_id) => {
+                    return IrPattern::Variant(ir_typedef_id, 0, ids);
+                }
                 _ => {}
             }
         }
@@ -327,6 +335,15 @@ fn process_pattern(
                 IrPattern::StringLiteral(v.clone())
             }
         }
+        Pattern::CharLiteral(v) => {
+            if irrefutable {
+                let err = ResolverError::NotIrrefutablePattern(location_id);
+                errors.push(err);
+                IrPattern::Wildcard
+            } else {
+                IrPattern::CharLiteral(*v)
+            }
+        }
         Pattern::Typed(pattern_id, type_signature_id) => {
             let ir_pattern_id = process_pattern(
                 case_expr_id,
@@ -434,6 +451,134 @@ fn process_pattern(
     ir_pattern_id
 }
 
+pub fn process_cases(
+    ir_body_id: IrExprId,
+    cases: &[Case],
+    program: &Program,
+    module: &Module,
+    environment: &mut Environment,
+    ir_program: &mut IrProgram,
+    errors: &mut Vec<ResolverError>,
+    lambda_helper: LambdaHelper,
+    type_arg_resolver: &mut TypeArgResolver,
+) -> (Vec<IrCase>, Vec<BindGroup>) {
+    let mut ir_cases = Vec::new();
+    let mut bind_groups = Vec::new();
+    for case in cases {
+        if let Pattern::Or(sub_patterns) = &program.patterns.get(&case.pattern_id).item {
+            let mut all_bindings = BTreeMap::new();
+            for sub_pattern in sub_patterns {
+                let mut case_environment = Environment::child(environment);
+                let mut bindings = BTreeMap::new();
+                let pattern_id = process_pattern(
+                    ir_body_id,
+                    *sub_pattern,
+                    program,
+                    ir_program,
+                    module,
+                    &mut case_environment,
+                    &mut bindings,
+                    errors,
+                    lambda_helper.clone(),
+                    false,
+                    type_arg_resolver,
+                );
+                for binding in bindings {
+                    if binding.1.len() > 1 {
+                        let err = ResolverError::PatternBindConflict(
+                            binding.0.clone(),
+                            binding.1.clone(),
+                        );
+                        errors.push(err);
+                    } else {
+                        let binding_data = all_bindings
+                            .entry(binding.0.clone())
+                            .or_insert_with(|| Vec::new());
+                        let binding_ref = case_environment
+                            .get_ref(&binding.0)
+                            .expect("Binding not found in env");
+                        if let NamedRef::ExprValue(_, pattern_id) = binding_ref.0 {
+                            binding_data.push((binding.1[0], pattern_id));
+                        } else {
+                            panic!("Pattern binding does not refer to a pattern")
+                        }
+                    }
+                }
+                let ir_case_body_id = process_expr(
+                    case.body,
+                    program,
+                    module,
+                    &mut case_environment,
+                    ir_program,
+                    errors,
+                    lambda_helper.clone(),
+                    type_arg_resolver,
+                );
+                let ir_case = IrCase {
+                    pattern_id: pattern_id,
+                    body: ir_case_body_id,
+                };
+                ir_cases.push(ir_case);
+            }
+            if errors.is_empty() {
+                for binding in all_bindings {
+                    if binding.1.len() != sub_patterns.len() {
+                        let err = ResolverError::PatternBindNotPresent(
+                            binding.0.clone(),
+                            binding.1[0].0.clone(),
+                        );
+                        errors.push(err);
+                    } else {
+                        let bind_group = BindGroup {
+                            patterns: binding.1.iter().map(|(_, id)| *id).collect(),
+                        };
+                        bind_groups.push(bind_group);
+                    }
+                }
+            }
+        } else {
+            let mut case_environment = Environment::child(environment);
+            let mut bindings = BTreeMap::new();
+            let pattern_id = process_pattern(
+                ir_body_id,
+                case.pattern_id,
+                program,
+                ir_program,
+                module,
+                &mut case_environment,
+                &mut bindings,
+                errors,
+                lambda_helper.clone(),
+                false,
+                type_arg_resolver,
+            );
+            for binding in bindings {
+                if binding.1.len() > 1 {
+                    let err =
+                        ResolverError::PatternBindConflict(binding.0.clone(), binding.1.clone());
+                    errors.push(err);
+                }
+            }
+            let ir_case_body_id = process_expr(
+                case.body,
+                program,
+                module,
+                &mut case_environment,
+                ir_program,
+                errors,
+                lambda_helper.clone(),
+                type_arg_resolver,
+            );
+            let ir_case = IrCase {
+                pattern_id: pattern_id,
+                body: ir_case_body_id,
+            };
+            ir_cases.push(ir_case);
+        }
+    }
+    (ir_cases, bind_groups)
+}
+
 pub fn process_expr(
     id: ExprId,
     program: &Program,
@@ -719,6 +864,10 @@ pub fn process_expr(
             let ir_expr = IrExpr::StringLiteral(v.clone());
             return add_expr(ir_expr, id, ir_program, program);
         }
+        Expr::CharLiteral(v) => {
+            let ir_expr = IrExpr::CharLiteral(*v);
+            return add_expr(ir_expr, id, ir_program, program);
+        }
         Expr::Do(items) => {
             let ir_items: Vec<IrExprId> = items
                 .iter()
@@ -832,122 +981,17 @@ pub fn process_expr(
                 lambda_helper.clone(),
                 type_arg_resolver,
             );
-            let mut ir_cases = Vec::new();
-            let mut bind_groups = Vec::new();
-            for case in cases {
-                if let Pattern::Or(sub_patterns) = &program.patterns.get(&case.pattern_id).item {
-                    let mut all_bindings = BTreeMap::new();
-                    for sub_pattern in sub_patterns {
-                        let mut case_environment = Environment::child(environment);
-                        let mut bindings = BTreeMap::new();
-                        let pattern_id = process_pattern(
-                            ir_body_id,
-                            *sub_pattern,
-                            program,
-                            ir_program,
-                            module,
-                            &mut case_environment,
-                            &mut bindings,
-                            errors,
-                            lambda_helper.clone(),
-                            false,
-                            type_arg_resolver,
-                        );
-                        for binding in bindings {
-                            if binding.1.len() > 1 {
-                                let err = ResolverError::PatternBindConflict(
-                                    binding.0.clone(),
-                                    binding.1.clone(),
-                                );
-                                errors.push(err);
-                            } else {
-                                let binding_data = all_bindings
-                                    .entry(binding.0.clone())
-                                    .or_insert_with(|| Vec::new());
-                                let binding_ref = case_environment
-                                    .get_ref(&binding.0)
-                                    .expect("Binding not found in env");
-                                if let NamedRef::ExprValue(_, pattern_id) = binding_ref.0 {
-                                    binding_data.push((binding.1[0], pattern_id));
-                                } else {
-                                    panic!("Pattern binding does not refer to a pattern")
-                                }
-                            }
-                        }
-                        let ir_case_body_id = process_expr(
-                            case.body,
-                            program,
-                            module,
-                            &mut case_environment,
-                            ir_program,
-                            errors,
-                            lambda_helper.clone(),
-                            type_arg_resolver,
-                        );
-                        let ir_case = IrCase {
-                            pattern_id: pattern_id,
-                            body: ir_case_body_id,
-                        };
-                        ir_cases.push(ir_case);
-                    }
-                    if errors.is_empty() {
-                        for binding in all_bindings {
-                            if binding.1.len() != sub_patterns.len() {
-                                let err = ResolverError::PatternBindNotPresent(
-                                    binding.0.clone(),
-                                    binding.1[0].0.clone(),
-                                );
-                                errors.push(err);
-                            } else {
-                                let bind_group = BindGroup {
-                                    patterns: binding.1.iter().map(|(_, id)| *id).collect(),
-                                };
-                                bind_groups.push(bind_group);
-                            }
-                        }
-                    }
-                } else {
-                    let mut case_environment = Environment::child(environment);
-                    let mut bindings = BTreeMap::new();
-                    let pattern_id = process_pattern(
-                        ir_body_id,
-                        case.pattern_id,
-                        program,
-                        ir_program,
-                        module,
-                        &mut case_environment,
-                        &mut bindings,
-                        errors,
-                        lambda_helper.clone(),
-                        false,
-                        type_arg_resolver,
-                    );
-                    for binding in bindings {
-                        if binding.1.len() > 1 {
-                            let err = ResolverError::PatternBindConflict(
-                                binding.0.clone(),
-                                binding.1.clone(),
-                            );
-                            errors.push(err);
-                        }
-                    }
-                    let ir_case_body_id = process_expr(
-                        case.body,
-                        program,
-                        module,
-                        &mut case_environment,
-                        ir_program,
-                        errors,
-                        lambda_helper.clone(),
-                        type_arg_resolver,
-                    );
-                    let ir_case = IrCase {
-                        pattern_id: pattern_id,
-                        body: ir_case_body_id,
-                    };
-                    ir_cases.push(ir_case);
-                }
-            }
+            let (ir_cases, bind_groups) = process_cases(
+                ir_body_id,
+                cases,
+                program,
+                module,
+                environment,
+                ir_program,
+                errors,
+                lambda_helper,
+                type_arg_resolver,
+            );
             let ir_expr = IrExpr::CaseOf(ir_body_id, ir_cases, bind_groups);
             return add_expr(ir_expr, id, ir_program, program);
         }
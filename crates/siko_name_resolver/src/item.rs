@@ -2,6 +2,8 @@ use siko_ir::class::ClassId as IrClassId;
 use siko_ir::class::ClassMemberId as IrClassMemberId;
 use siko_ir::data::TypeDefId;
 use siko_ir::function::FunctionId as IrFunctionId;
+use siko_syntax::actor::ActorId;
+use siko_syntax::actor::ProtocolId;
 use siko_syntax::class::ClassId;
 use siko_syntax::data::AdtId;
 use siko_syntax::data::RecordFieldId;
@@ -18,6 +20,8 @@ pub enum Item {
     Variant(AdtId, VariantId, TypeDefId, usize),
     Class(ClassId, IrClassId),
     ClassMember(ClassId, FunctionTypeId, IrClassMemberId),
+    Protocol(ProtocolId, TypeDefId),
+    Actor(ActorId, TypeDefId),
 }
 
 #[derive(Debug, Clone)]
@@ -2,6 +2,7 @@ use crate::environment::Environment;
 use crate::error::Error;
 use crate::error::ResolverError;
 use crate::export_processor::process_exports;
+use crate::expr_processor::process_cases;
 use crate::expr_processor::process_expr;
 use crate::import_processor::process_imports;
 use crate::item::DataMember;
@@ -15,6 +16,8 @@ use crate::type_processor::collect_type_args;
 use crate::type_processor::process_class_type_signature;
 use crate::type_processor::process_type_signature;
 use crate::type_processor::subtitute_type_signature;
+use siko_ir::actor::Actor as IrActor;
+use siko_ir::actor::ProtocolHandler as IrProtocolHandler;
 use siko_ir::class::Class as IrClass;
 use siko_ir::class::ClassId as IrClassId;
 use siko_ir::class::ClassMember as IrClassMember;
@@ -28,6 +31,8 @@ use siko_ir::data::TypeDef;
 use siko_ir::data::TypeDefId;
 use siko_ir::data::Variant as IrVariant;
 use siko_ir::data::VariantItem;
+use siko_ir::expr::Expr as IrExpr;
+use siko_ir::expr::ExprId as IrExprId;
 use siko_ir::function::Function as IrFunction;
 use siko_ir::function::FunctionId as IrFunctionId;
 use siko_ir::function::FunctionInfo;
@@ -39,14 +44,19 @@ use siko_ir::program::Program as IrProgram;
 use siko_ir::type_signature::TypeSignature;
 use siko_ir::type_signature::TypeSignatureId;
 use siko_ir::type_var_generator::TypeVarGenerator;
+use siko_location_info::item::ItemInfo;
 use siko_location_info::location_id::LocationId;
+use siko_syntax::actor::ActorId;
+use siko_syntax::actor::ProtocolId;
 use siko_syntax::class::ClassId as AstClassId;
 use siko_syntax::class::Instance as AstInstance;
 use siko_syntax::data::AdtId;
 use siko_syntax::data::DerivedClass as AstDerivedClass;
 use siko_syntax::data::RecordId;
+use siko_syntax::expr::ExprId as AstExprId;
 use siko_syntax::function::Function as AstFunction;
 use siko_syntax::function::FunctionBody as AstFunctionBody;
+use siko_syntax::function::FunctionClause as AstFunctionClause;
 use siko_syntax::function::FunctionId as AstFunctionId;
 use siko_syntax::function::FunctionType as AstFunctionType;
 use siko_syntax::function::FunctionTypeId as AstFunctionTypeId;
@@ -240,6 +250,38 @@ impl Resolver {
                     module.add_member(ast_variant.name.clone(), member);
                 }
             }
+            for protocol_id in &ast_module.protocols {
+                let protocol = program.protocols.get(protocol_id);
+                let ir_typedef_id = ir_program.typedefs.get_id();
+                let ir_adt = Adt {
+                    name: protocol.name.clone(),
+                    module: ast_module.name.clone(),
+                    id: ir_typedef_id,
+                    type_args: Vec::new(),
+                    variants: Vec::new(),
+                    derived_classes: Vec::new(),
+                };
+                let typedef = TypeDef::Adt(ir_adt);
+                ir_program.typedefs.add_item(ir_typedef_id, typedef);
+                let item = Item::Protocol(*protocol_id, ir_typedef_id);
+                module.add_item(protocol.name.clone(), item);
+            }
+            for actor_id in &ast_module.actors {
+                let actor = program.actors.get(actor_id);
+                let ir_typedef_id = ir_program.typedefs.get_id();
+                let ir_adt = Adt {
+                    name: actor.name.clone(),
+                    module: ast_module.name.clone(),
+                    id: ir_typedef_id,
+                    type_args: Vec::new(),
+                    variants: Vec::new(),
+                    derived_classes: Vec::new(),
+                };
+                let typedef = TypeDef::Adt(ir_adt);
+                ir_program.typedefs.add_item(ir_typedef_id, typedef);
+                let item = Item::Actor(*actor_id, ir_typedef_id);
+                module.add_item(actor.name.clone(), item);
+            }
             for (_, function_ids) in &ast_module.functions {
                 for function_id in function_ids {
                     let function = program.functions.get(function_id);
@@ -268,7 +310,7 @@ impl Resolver {
                     }
                 }
                 let auto_derivable_classes: Vec<_> =
-                    vec!["PartialEq", "Eq", "PartialOrd", "Ord", "Show"];
+                    vec!["PartialEq", "Eq", "PartialOrd", "Ord", "Show", "Enum"];
                 let auto_derivable = module.name == "Std.Ops"
                     && auto_derivable_classes.contains(&class.name.as_ref());
                 let ir_class = IrClass {
@@ -319,6 +361,14 @@ impl Resolver {
                                 let class_member = program.function_types.get(id);
                                 locations.push(class_member.location_id);
                             }
+                            Item::Protocol(id, _) => {
+                                let protocol = program.protocols.get(id);
+                                locations.push(protocol.location_id);
+                            }
+                            Item::Actor(id, _) => {
+                                let actor = program.actors.get(id);
+                                locations.push(actor.location_id);
+                            }
                         }
                     }
                     if items.len() == 2 && adt_found && variant_found {
@@ -409,6 +459,65 @@ impl Resolver {
         (result, type_arg_resolver)
     }
 
+    // Multiple equations are lowered into a case expression matching on the tuple of the arguments
+    fn process_function_clauses(
+        &self,
+        args_expr_id: AstExprId,
+        clauses: &[AstFunctionClause],
+        program: &Program,
+        ir_program: &mut IrProgram,
+        function: &AstFunction,
+        module: &Module,
+        environment: &mut Environment,
+        errors: &mut Vec<ResolverError>,
+        lambda_helper: LambdaHelper,
+        type_arg_resolver: &mut TypeArgResolver,
+    ) -> IrExprId {
+        let ir_args_expr_id = process_expr(
+            args_expr_id,
+            program,
+            module,
+            environment,
+            ir_program,
+            errors,
+            lambda_helper.clone(),
+            type_arg_resolver,
+        );
+        let mut mismatch = false;
+        for clause in clauses {
+            if clause.args.len() != function.args.len() {
+                let err = ResolverError::FunctionClauseArgumentMismatch(
+                    function.name.clone(),
+                    function.args.len(),
+                    clause.args.len(),
+                    clause.location_id,
+                );
+                errors.push(err);
+                mismatch = true;
+            }
+        }
+        if mismatch {
+            return ir_args_expr_id;
+        }
+        let cases: Vec<_> = clauses.iter().map(|clause| clause.case.clone()).collect();
+        let (ir_cases, bind_groups) = process_cases(
+            ir_args_expr_id,
+            &cases,
+            program,
+            module,
+            environment,
+            ir_program,
+            errors,
+            lambda_helper,
+            type_arg_resolver,
+        );
+        let ir_expr = IrExpr::CaseOf(ir_args_expr_id, ir_cases, bind_groups);
+        let ir_expr_id = ir_program.exprs.get_id();
+        let expr_info = ItemInfo::new(ir_expr, function.location_id);
+        ir_program.exprs.add_item(ir_expr_id, expr_info);
+        ir_expr_id
+    }
+
     fn process_function(
         &self,
         program: &Program,
@@ -423,7 +532,12 @@ impl Resolver {
     ) {
         let mut body = None;
 
-        if let AstFunctionBody::Expr(id) = function.body {
+        let is_extern = match function.body {
+            AstFunctionBody::Extern => true,
+            _ => false,
+        };
+
+        if !is_extern {
             let mut environment = Environment::new();
             let mut arg_names = BTreeSet::new();
             let mut conflicting_names = BTreeSet::new();
@@ -449,16 +563,31 @@ impl Resolver {
                 ir_function_id,
                 None,
             );
-            let body_id = process_expr(
-                id,
-                program,
-                module,
-                &mut environment,
-                ir_program,
-                errors,
-                lambda_helper,
-                type_arg_resolver,
-            );
+            let body_id = match &function.body {
+                AstFunctionBody::Expr(id) => process_expr(
+                    *id,
+                    program,
+                    module,
+                    &mut environment,
+                    ir_program,
+                    errors,
+                    lambda_helper,
+                    type_arg_resolver,
+                ),
+                AstFunctionBody::Clauses(args_expr_id, clauses) => self.process_function_clauses(
+                    *args_expr_id,
+                    clauses,
+                    program,
+                    ir_program,
+                    function,
+                    module,
+                    &mut environment,
+                    errors,
+                    lambda_helper,
+                    type_arg_resolver,
+                ),
+                AstFunctionBody::Extern => unreachable!(),
+            };
             body = Some(body_id);
         }
 
@@ -585,6 +714,226 @@ impl Resolver {
         }
     }
 
+    fn process_protocol(
+        &self,
+        program: &Program,
+        ir_program: &mut IrProgram,
+        protocol_id: &ProtocolId,
+        ir_typedef_id: TypeDefId,
+        module: &Module,
+        errors: &mut Vec<ResolverError>,
+    ) {
+        let protocol = program.protocols.get(protocol_id);
+
+        let mut type_arg_resolver = TypeArgResolver::new(self.type_var_generator.clone());
+
+        let result = process_type_signature(
+            &protocol.type_signature,
+            program,
+            ir_program,
+            module,
+            &mut type_arg_resolver,
+            errors,
+        );
+
+        if let Some(message_type_signature_id) = result {
+            let location_id = ir_program
+                .type_signatures
+                .get(&message_type_signature_id)
+                .location_id;
+            let ir_typesignature_id = ir_program.type_signatures.get_id();
+            let signature =
+                TypeSignature::Variant(protocol.name.clone(), vec![message_type_signature_id]);
+            ir_program
+                .type_signatures
+                .add_item(ir_typesignature_id, ItemInfo::new(signature, location_id));
+            let ir_ctor_id = ir_program.functions.get_id();
+            let variant_ctor_info = VariantConstructorInfo {
+                type_id: ir_typedef_id,
+                index: 0,
+            };
+            let ir_ctor_function = IrFunction {
+                id: ir_ctor_id,
+                arg_locations: vec![location_id],
+                implicit_arg_count: 0,
+                info: FunctionInfo::VariantConstructor(variant_ctor_info),
+            };
+            ir_program.functions.add_item(ir_ctor_id, ir_ctor_function);
+
+            let ir_variant = IrVariant {
+                name: protocol.name.clone(),
+                items: vec![VariantItem {
+                    type_signature_id: message_type_signature_id,
+                }],
+                type_signature_id: ir_typesignature_id,
+                constructor: ir_ctor_id,
+            };
+
+            let ir_adt = ir_program.typedefs.get_mut(&ir_typedef_id).get_mut_adt();
+            ir_adt.variants = vec![ir_variant];
+        }
+    }
+
+    fn process_actor(
+        &self,
+        program: &Program,
+        ir_program: &mut IrProgram,
+        actor_id: &ActorId,
+        ir_typedef_id: TypeDefId,
+        module: &Module,
+        errors: &mut Vec<ResolverError>,
+    ) {
+        let actor = program.actors.get(actor_id);
+
+        let mut type_arg_resolver = TypeArgResolver::new(self.type_var_generator.clone());
+
+        let result = process_type_signature(
+            &actor.type_signature,
+            program,
+            ir_program,
+            module,
+            &mut type_arg_resolver,
+            errors,
+        );
+
+        let mut handlers = Vec::new();
+        let mut handler_locations = BTreeMap::new();
+
+        for handler in &actor.handlers {
+            let locations = handler_locations
+                .entry(handler.protocol.clone())
+                .or_insert_with(|| Vec::new());
+            locations.push(handler.protocol_location_id);
+            let protocol_id = match module.imported_items.get(&handler.protocol) {
+                Some(items) => {
+                    if items.len() > 1 {
+                        let err = ResolverError::AmbiguousName(
+                            handler.protocol.clone(),
+                            handler.protocol_location_id,
+                        );
+                        errors.push(err);
+                        None
+                    } else if let Item::Protocol(_, ir_typedef_id) = items[0].item {
+                        Some(ir_typedef_id)
+                    } else {
+                        let err = ResolverError::UnknownProtocol(
+                            handler.protocol.clone(),
+                            handler.protocol_location_id,
+                        );
+                        errors.push(err);
+                        None
+                    }
+                }
+                None => {
+                    let err = ResolverError::UnknownProtocol(
+                        handler.protocol.clone(),
+                        handler.protocol_location_id,
+                    );
+                    errors.push(err);
+                    None
+                }
+            };
+            let handler_func = match module.imported_items.get(&handler.handler_func) {
+                Some(items) => {
+                    if items.len() > 1 {
+                        let err = ResolverError::AmbiguousName(
+                            handler.handler_func.clone(),
+                            handler.handler_func_location_id,
+                        );
+                        errors.push(err);
+                        None
+                    } else if let Item::Function(_, ir_function_id) = items[0].item {
+                        Some(ir_function_id)
+                    } else {
+                        let err = ResolverError::UnknownProtocolHandler(
+                            handler.handler_func.clone(),
+                            handler.handler_func_location_id,
+                        );
+                        errors.push(err);
+                        None
+                    }
+                }
+                None => {
+                    let err = ResolverError::UnknownProtocolHandler(
+                        handler.handler_func.clone(),
+                        handler.handler_func_location_id,
+                    );
+                    errors.push(err);
+                    None
+                }
+            };
+            if let (Some(protocol_id), Some(handler_func)) = (protocol_id, handler_func) {
+                let ir_handler = IrProtocolHandler {
+                    protocol_id: protocol_id,
+                    protocol_location_id: handler.protocol_location_id,
+                    handler_func: handler_func,
+                    handler_func_location_id: handler.handler_func_location_id,
+                };
+                handlers.push(ir_handler);
+            }
+        }
+
+        for (protocol, locations) in handler_locations {
+            if locations.len() > 1 {
+                let err = ResolverError::ConflictingProtocolHandlers(
+                    actor.name.clone(),
+                    protocol,
+                    locations,
+                );
+                errors.push(err);
+            }
+        }
+
+        if let Some(state_type_signature_id) = result {
+            let location_id = ir_program
+                .type_signatures
+                .get(&state_type_signature_id)
+                .location_id;
+            let ir_typesignature_id = ir_program.type_signatures.get_id();
+            let signature =
+                TypeSignature::Variant(actor.name.clone(), vec![state_type_signature_id]);
+            ir_program
+                .type_signatures
+                .add_item(ir_typesignature_id, ItemInfo::new(signature, location_id));
+            let ir_ctor_id = ir_program.functions.get_id();
+            let variant_ctor_info = VariantConstructorInfo {
+                type_id: ir_typedef_id,
+                index: 0,
+            };
+            let ir_ctor_function = IrFunction {
+                id: ir_ctor_id,
+                arg_locations: vec![location_id],
+                implicit_arg_count: 0,
+                info: FunctionInfo::VariantConstructor(variant_ctor_info),
+            };
+            ir_program.functions.add_item(ir_ctor_id, ir_ctor_function);
+
+            let ir_variant = IrVariant {
+                name: actor.name.clone(),
+                items: vec![VariantItem {
+                    type_signature_id: state_type_signature_id,
+                }],
+                type_signature_id: ir_typesignature_id,
+                constructor: ir_ctor_id,
+            };
+
+            let ir_adt = ir_program.typedefs.get_mut(&ir_typedef_id).get_mut_adt();
+            ir_adt.variants = vec![ir_variant];
+
+            let id = ir_program.actors.get_id();
+            let ir_actor = IrActor {
+                id: id,
+                name: actor.name.clone(),
+                module: module.name.clone(),
+                type_id: ir_typedef_id,
+                type_signature: state_type_signature_id,
+                handlers: handlers,
+                location_id: actor.location_id,
+            };
+            ir_program.actors.add_item(id, ir_actor);
+        }
+    }
+
     fn process_record(
         &self,
         program: &Program,
@@ -1127,6 +1476,14 @@ impl Resolver {
                             module,
                             &mut errors,
                         ),
+                        Item::Protocol(ast_protocol_id, ir_typedef_id) => self.process_protocol(
+                            program,
+                            &mut ir_program,
+                            ast_protocol_id,
+                            *ir_typedef_id,
+                            module,
+                            &mut errors,
+                        ),
                         _ => {}
                     }
                 }
@@ -1160,6 +1517,27 @@ impl Resolver {
             return Err(Error::resolve_err(errors));
         }
 
+        for (_, module) in &self.modules {
+            for (_, items) in &module.items {
+                for item in items {
+                    if let Item::Actor(ast_actor_id, ir_typedef_id) = item {
+                        self.process_actor(
+                            program,
+                            &mut ir_program,
+                            ast_actor_id,
+                            *ir_typedef_id,
+                            module,
+                            &mut errors,
+                        );
+                    }
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(Error::resolve_err(errors));
+        }
+
         for (_, module) in &self.modules {
             let ast_module = program.modules.get(&module.id);
             let (_, function_types_without_functions, _, conflicting_function_types) =
@@ -81,6 +81,20 @@ fn process_named_type(
                     }
                     IrTypeSignature::Named(ir_record.name.clone(), ir_typedef_id, named_arg_ids)
                 }
+                Item::Protocol(_, ir_typedef_id) | Item::Actor(_, ir_typedef_id) => {
+                    let ir_adt = ir_program.typedefs.get(&ir_typedef_id).get_adt();
+                    if !named_arg_ids.is_empty() {
+                        let err = ResolverError::IncorrectTypeArgumentCount(
+                            name.to_string(),
+                            0,
+                            named_arg_ids.len(),
+                            location_id,
+                        );
+                        errors.push(err);
+                        return None;
+                    }
+                    IrTypeSignature::Named(ir_adt.name.clone(), ir_typedef_id, named_arg_ids)
+                }
                 Item::Function(..)
                 | Item::Variant(..)
                 | Item::ClassMember(..)
@@ -6,6 +6,8 @@ use crate::parser::Parser;
 use crate::token::Token;
 use crate::token::TokenKind;
 use siko_constants::BuiltinOperator;
+use siko_constants::ENUM_FROM_THEN_TO;
+use siko_constants::ENUM_FROM_TO;
 use siko_syntax::expr::Case;
 use siko_syntax::expr::Expr;
 use siko_syntax::expr::ExprId;
@@ -39,6 +41,9 @@ fn parse_list_expr(parser: &mut Parser) -> Result<ExprId, ParseError> {
         }
         let expr = parser.parse_expr()?;
         items.push(expr);
+        if items.len() <= 2 && parser.current(TokenKind::DoubleDot) {
+            return parse_range(parser, items, start_index);
+        }
         if parser.current(TokenKind::Comma) {
             parser.expect(TokenKind::Comma)?;
             continue;
@@ -50,6 +55,28 @@ fn parse_list_expr(parser: &mut Parser) -> Result<ExprId, ParseError> {
     return Ok(id);
 }
 
+// [from..to] and [from, next..to] are lowered to calls of the enum range functions of Std.Ops
+fn parse_range(
+    parser: &mut Parser,
+    mut items: Vec<ExprId>,
+    start_index: usize,
+) -> Result<ExprId, ParseError> {
+    parser.expect(TokenKind::DoubleDot)?;
+    let to = parser.parse_expr()?;
+    parser.expect(TokenKind::RBracket)?;
+    let name = if items.len() == 1 {
+        ENUM_FROM_TO
+    } else {
+        ENUM_FROM_THEN_TO
+    };
+    let function_expr = Expr::Path(name.to_string());
+    let function_expr_id = parser.add_expr(function_expr, start_index);
+    items.push(to);
+    let expr = Expr::FunctionCall(function_expr_id, items);
+    let id = parser.add_expr(expr, start_index);
+    return Ok(id);
+}
+
 fn parse_lambda(parser: &mut Parser) -> Result<ExprId, ParseError> {
     let start_index = parser.get_index();
     parser.expect(TokenKind::Lambda)?;
@@ -189,6 +216,17 @@ fn parse_sub_pattern(parser: &mut Parser, inner: bool) -> Result<Option<PatternI
                 unreachable!()
             }
         }
+        TokenKind::CharLiteral => {
+            let start_index = parser.get_index();
+            let literal = parser.advance()?;
+            if let Token::CharLiteral(c) = literal.token {
+                let pattern = Pattern::CharLiteral(c);
+                let id = parser.add_pattern(pattern, start_index);
+                id
+            } else {
+                unreachable!()
+            }
+        }
         TokenKind::VarIdentifier => {
             let start_index = parser.get_index();
             let name = parser.var_identifier("pattern binding")?;
@@ -200,7 +238,13 @@ fn parse_sub_pattern(parser: &mut Parser, inner: bool) -> Result<Option<PatternI
             if inner {
                 let start_index = parser.get_index();
                 let name = parser.parse_qualified_type_name()?;
-                let id = parser.add_pattern(Pattern::Constructor(name, Vec::new()), start_index);
+                let pattern = if parser.current(TokenKind::LCurly) {
+                    let items = parser.parse_list0_in_curly_parens(parse_record_field_pattern)?;
+                    Pattern::Record(name, items)
+                } else {
+                    Pattern::Constructor(name, Vec::new())
+                };
+                let id = parser.add_pattern(pattern, start_index);
                 id
             } else {
                 let start_index = parser.get_index();
@@ -249,6 +293,15 @@ pub fn parse_pattern(parser: &mut Parser) -> Result<PatternId, ParseError> {
     }
 }
 
+// Constructors do not take arguments without parentheses in argument position
+pub fn parse_arg_pattern(parser: &mut Parser) -> Result<PatternId, ParseError> {
+    let id = parse_sub_pattern(parser, true)?;
+    match id {
+        Some(id) => Ok(id),
+        None => report_unexpected_token(parser, format!("<pattern>")),
+    }
+}
+
 fn parse_case(parser: &mut Parser) -> Result<ExprId, ParseError> {
     let start_index = parser.get_index();
     parser.expect(TokenKind::KeywordCase)?;
@@ -379,10 +432,16 @@ fn parse_arg(parser: &mut Parser) -> Result<ExprId, ParseError> {
             let id = parser.add_expr(expr, start_index);
             id
         }
+        Token::CharLiteral(c) => {
+            parser.advance()?;
+            let expr = Expr::CharLiteral(c);
+            let id = parser.add_expr(expr, start_index);
+            id
+        }
         Token::StringLiteral(s) => {
             parser.advance()?;
-            if parser.current(TokenKind::Formatter) {
-                parser.expect(TokenKind::Formatter)?;
+            if parser.current(TokenKind::Op(BuiltinOperator::Rem)) {
+                parser.expect(TokenKind::Op(BuiltinOperator::Rem))?;
                 let items = if parser.current(TokenKind::LParen) {
                     parser.parse_list1_in_parens(|p| parse_ops(p))?
                 } else {
@@ -435,6 +494,7 @@ fn parse_primary(parser: &mut Parser) -> Result<ExprId, ParseError> {
             | TokenKind::IntegerLiteral
             | TokenKind::FloatLiteral
             | TokenKind::StringLiteral
+            | TokenKind::CharLiteral
             | TokenKind::LParen
             | TokenKind::KeywordIf
             | TokenKind::KeywordDo
@@ -462,14 +522,14 @@ fn parse_unary(parser: &mut Parser, is_arg: bool) -> Result<ExprId, ParseError>
         &[BuiltinOperator::Not, BuiltinOperator::Sub]
     };
     if let Some((op, _)) = parser.consume_op(ops) {
-        let function_id_expr = Expr::Builtin(op);
-        let function_id_expr_id = parser.add_expr(function_id_expr, start_index);
-        let right = parse_unary(parser, is_arg)?;
         let op = if op == BuiltinOperator::Sub {
             BuiltinOperator::Minus
         } else {
             op
         };
+        let function_id_expr = Expr::Builtin(op);
+        let function_id_expr_id = parser.add_expr(function_id_expr, start_index);
+        let right = parse_unary(parser, is_arg)?;
         if op == BuiltinOperator::Minus {
             let right_expr_info = parser.get_program().exprs.get_mut(&right);
             // FIXME: fix location of these literals
@@ -557,7 +617,11 @@ fn parse_addsub(parser: &mut Parser) -> Result<ExprId, ParseError> {
 fn parse_muldiv(parser: &mut Parser) -> Result<ExprId, ParseError> {
     return parse_binary_op(
         parser,
-        &[BuiltinOperator::Mul, BuiltinOperator::Div],
+        &[
+            BuiltinOperator::Mul,
+            BuiltinOperator::Div,
+            BuiltinOperator::Rem,
+        ],
         parse_pipe_forward,
     );
 }
@@ -254,6 +254,83 @@ impl Lexer {
         Ok(())
     }
 
+    fn collect_char_escape(&mut self) -> Result<char, LexerError> {
+        let start = self.line_offset;
+        let c = self.advance()?;
+        let special = match c {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            '\\' => Some('\\'),
+            '\'' => Some('\''),
+            '"' => Some('"'),
+            'u' => {
+                let mut digits = String::new();
+                if self.peek()? == '{' {
+                    self.advance()?;
+                    while !self.is_done() && self.peek()?.is_ascii_hexdigit() {
+                        digits.push(self.advance()?);
+                    }
+                    if !self.is_done() && self.peek()? == '}' {
+                        self.advance()?;
+                    } else {
+                        digits.clear();
+                    }
+                }
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(std::char::from_u32)
+            }
+            _ => None,
+        };
+        match special {
+            Some(special) => Ok(special),
+            None => Err(LexerError::General(
+                format!("Invalid escape sequence \\{}", c),
+                self.file_path.clone(),
+                Location::new(self.line_index, Span::new(start - 1, self.line_offset)),
+            )),
+        }
+    }
+
+    fn collect_char_literal(&mut self) -> Result<(), LexerError> {
+        let start = self.line_offset;
+        let mut literal = Vec::new();
+        let mut inside = true;
+        self.advance()?;
+        while !self.is_done() {
+            let c = self.peek()?;
+            if c == '\'' {
+                inside = false;
+                self.advance()?;
+                break;
+            }
+            if c == '\n' {
+                break;
+            }
+            self.advance()?;
+            if c == '\\' {
+                literal.push(self.collect_char_escape()?);
+            } else {
+                literal.push(c);
+            }
+        }
+        let span = Span {
+            start: start,
+            end: self.line_offset,
+        };
+        if inside || literal.len() != 1 {
+            return Err(LexerError::General(
+                format!("Invalid char literal"),
+                self.file_path.clone(),
+                Location::new(self.line_index, span),
+            ));
+        }
+        self.add_token(Token::CharLiteral(literal[0]), span);
+        Ok(())
+    }
+
     fn process_line_comment(&mut self) -> Result<(), LexerError> {
         while !self.is_done() {
             let c = self.peek()?;
@@ -338,6 +415,8 @@ impl Lexer {
                 self.collect_operator()?;
             } else if c == '"' {
                 self.collect_string_literal()?;
+            } else if c == '\'' {
+                self.collect_char_literal()?;
             } else {
                 let span = Span::single(self.line_offset);
                 let t = match c {
@@ -362,7 +441,7 @@ impl Lexer {
                     '[' => Token::LBracket,
                     ']' => Token::RBracket,
                     ';' => Token::Semicolon,
-                    '%' => Token::Formatter,
+                    '%' => Token::Op(BuiltinOperator::Rem),
                     _ => {
                         let err = LexerError::UnsupportedCharacter(
                             c,
@@ -1,3 +1,4 @@
+use super::expr::parse_arg_pattern;
 use super::expr::parse_ops;
 use super::expr::parse_pattern;
 use super::util::parse_parens;
@@ -19,6 +20,7 @@ use siko_constants::BOOL_MODULE_NAME;
 use siko_constants::INT_MODULE_NAME;
 use siko_constants::FLOAT_MODULE_NAME;
 use siko_constants::STRING_MODULE_NAME;
+use siko_constants::CHAR_MODULE_NAME;
 use siko_location_info::filepath::FilePath;
 use siko_location_info::item::Item;
 use siko_location_info::item::ItemInfo;
@@ -46,10 +48,12 @@ use siko_syntax::export_import::EIItemInfo;
 use siko_syntax::export_import::EIList;
 use siko_syntax::export_import::EIMember;
 use siko_syntax::export_import::EIMemberInfo;
+use siko_syntax::expr::Case;
 use siko_syntax::expr::Expr;
 use siko_syntax::expr::ExprId;
 use siko_syntax::function::Function;
 use siko_syntax::function::FunctionBody;
+use siko_syntax::function::FunctionClause;
 use siko_syntax::function::FunctionId;
 use siko_syntax::function::FunctionType;
 use siko_syntax::function::FunctionTypeId;
@@ -259,10 +263,11 @@ impl<'a> Parser<'a> {
                 | TokenKind::IntegerLiteral
                 | TokenKind::FloatLiteral
                 | TokenKind::StringLiteral
+                | TokenKind::CharLiteral
                 | TokenKind::VarIdentifier
                 | TokenKind::TypeIdentifier
                 | TokenKind::Wildcard => {
-                    let arg = parse_pattern(self)?;
+                    let arg = parse_arg_pattern(self)?;
                     args.push(arg);
                 }
                 _ => {
@@ -374,6 +379,63 @@ impl<'a> Parser<'a> {
         Ok(items)
     }
 
+    fn parse_function_guard(&mut self) -> Result<Option<ExprId>, ParseError> {
+        if self.current(TokenKind::KeywordIf) {
+            self.expect(TokenKind::KeywordIf)?;
+            let guard_expr = self.parse_expr()?;
+            Ok(Some(guard_expr))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn function_clause_follows(&self, name: &str) -> bool {
+        match self.peek() {
+            Some(token) => match &token.token {
+                Token::VarIdentifier(n) if n == name => {}
+                _ => return false,
+            },
+            None => return false,
+        }
+        let mut index = self.index + 1;
+        let mut depth = 0;
+        while index < self.tokens.len() {
+            match self.tokens[index].token.kind() {
+                TokenKind::LParen => depth += 1,
+                TokenKind::RParen => depth -= 1,
+                TokenKind::KeywordDoubleColon if depth == 0 => return false,
+                TokenKind::Equal | TokenKind::EndOfItem => return true,
+                _ => {}
+            }
+            index += 1;
+        }
+        false
+    }
+
+    fn create_function_clause(
+        &mut self,
+        args: Vec<PatternId>,
+        guard: Option<ExprId>,
+        body: ExprId,
+        location_id: LocationId,
+        start_index: usize,
+    ) -> FunctionClause {
+        let tuple_pattern = Pattern::Tuple(args.clone());
+        let mut pattern_id = self.add_pattern(tuple_pattern, start_index);
+        if let Some(guard) = guard {
+            let guarded_pattern = Pattern::Guarded(pattern_id, guard);
+            pattern_id = self.add_pattern(guarded_pattern, start_index);
+        }
+        FunctionClause {
+            args: args,
+            case: Case {
+                pattern_id: pattern_id,
+                body: body,
+            },
+            location_id: location_id,
+        }
+    }
+
     pub fn consume_op(
         &mut self,
         op_kinds: &[BuiltinOperator],
@@ -613,10 +675,11 @@ impl<'a> Parser<'a> {
         } else {
             let end_index = self.get_index();
             let location_id = self.get_location_id(start_index, end_index);
+            let guard = self.parse_function_guard()?;
             self.expect(TokenKind::Equal)?;
             let mut temp_args = Vec::new();
             let body = if let Some(token) = self.peek() {
-                if token.token.kind() == TokenKind::KeywordExtern {
+                if guard.is_none() && token.token.kind() == TokenKind::KeywordExtern {
                     self.expect(TokenKind::KeywordExtern)?;
                     for arg in args {
                         let item_info = &self.program.patterns.get(&arg);
@@ -643,10 +706,53 @@ impl<'a> Parser<'a> {
                         }
                     }
 
+                    self.expect(TokenKind::EndOfItem)?;
                     FunctionBody::Extern
                 } else {
                     let body_expr_id = self.parse_expr()?;
-                    if args.is_empty() {
+                    self.expect(TokenKind::EndOfItem)?;
+                    let mut clauses = vec![self.create_function_clause(
+                        args.clone(),
+                        guard,
+                        body_expr_id,
+                        location_id,
+                        start_index,
+                    )];
+                    // consecutive equations of the same function are grouped together
+                    while !args.is_empty() && self.function_clause_follows(&name) {
+                        let clause_start_index = self.get_index();
+                        self.var_identifier("function name")?;
+                        let clause_args = self.parse_pattern_args()?;
+                        let clause_end_index = self.get_index();
+                        let clause_location_id =
+                            self.get_location_id(clause_start_index, clause_end_index);
+                        let clause_guard = self.parse_function_guard()?;
+                        self.expect(TokenKind::Equal)?;
+                        let clause_body = self.parse_expr()?;
+                        self.expect(TokenKind::EndOfItem)?;
+                        let clause = self.create_function_clause(
+                            clause_args,
+                            clause_guard,
+                            clause_body,
+                            clause_location_id,
+                            clause_start_index,
+                        );
+                        clauses.push(clause);
+                    }
+                    if clauses.len() > 1 || guard.is_some() {
+                        let mut temp_arg_exprs = Vec::new();
+                        for arg in args.iter() {
+                            let location = self.get_program().patterns.get(arg).location_id;
+                            let temp_arg_name = self.get_temp_var_name();
+                            temp_args.push((temp_arg_name.clone(), location));
+                            let path_expr = Expr::Path(temp_arg_name);
+                            let path_expr_id = self.add_expr(path_expr, start_index);
+                            temp_arg_exprs.push(path_expr_id);
+                        }
+                        let tuple_expr = Expr::Tuple(temp_arg_exprs);
+                        let tuple_expr_id = self.add_expr(tuple_expr, start_index);
+                        FunctionBody::Clauses(tuple_expr_id, clauses)
+                    } else if args.is_empty() {
                         FunctionBody::Expr(body_expr_id)
                     } else {
                         let mut temp_arg_exprs = Vec::new();
@@ -672,7 +778,6 @@ impl<'a> Parser<'a> {
             } else {
                 unreachable!()
             };
-            self.expect(TokenKind::EndOfItem)?;
             let id = self.program.functions.get_id();
             let function = Function {
                 id: id,
@@ -1281,6 +1386,7 @@ impl<'a> Parser<'a> {
             INT_MODULE_NAME,
             FLOAT_MODULE_NAME,
             STRING_MODULE_NAME,
+            CHAR_MODULE_NAME,
             BOOL_MODULE_NAME,
             ORDERING_MODULE_NAME,
             OPTION_MODULE_NAME,
@@ -6,6 +6,7 @@ pub enum Token {
     VarIdentifier(String),
     TypeIdentifier(String),
     StringLiteral(String),
+    CharLiteral(char),
     IntegerLiteral(i64),
     FloatLiteral(f64),
     KeywordWhere,
@@ -42,7 +43,6 @@ pub enum Token {
     Lambda,
     Dot,
     DoubleDot,
-    Formatter,
     Wildcard,
     Colon,
     EndOfItem,
@@ -64,6 +64,7 @@ impl Token {
             Token::VarIdentifier(..) => TokenKind::VarIdentifier,
             Token::TypeIdentifier(..) => TokenKind::TypeIdentifier,
             Token::StringLiteral(..) => TokenKind::StringLiteral,
+            Token::CharLiteral(..) => TokenKind::CharLiteral,
             Token::IntegerLiteral(..) => TokenKind::IntegerLiteral,
             Token::FloatLiteral(..) => TokenKind::FloatLiteral,
             Token::KeywordWhere => TokenKind::KeywordWhere,
@@ -100,7 +101,6 @@ impl Token {
             Token::Lambda => TokenKind::Lambda,
             Token::Dot => TokenKind::Dot,
             Token::DoubleDot => TokenKind::DoubleDot,
-            Token::Formatter => TokenKind::Formatter,
             Token::Wildcard => TokenKind::Wildcard,
             Token::Colon => TokenKind::Colon,
             Token::EndOfItem => TokenKind::EndOfItem,
@@ -115,6 +115,7 @@ pub enum TokenKind {
     VarIdentifier,
     TypeIdentifier,
     StringLiteral,
+    CharLiteral,
     IntegerLiteral,
     FloatLiteral,
     KeywordWhere,
@@ -151,7 +152,6 @@ pub enum TokenKind {
     Lambda,
     Dot,
     DoubleDot,
-    Formatter,
     Wildcard,
     Colon,
     EndOfItem,
@@ -176,6 +176,7 @@ impl TokenKind {
             TokenKind::Op(BuiltinOperator::Arrow) => format!("->"),
             TokenKind::KeywordConstraint => format!("=>"),
             TokenKind::StringLiteral => format!("string literal"),
+            TokenKind::CharLiteral => format!("char literal"),
             TokenKind::FloatLiteral => format!("float literal"),
             TokenKind::IntegerLiteral => format!("integer literal"),
             _ => {
@@ -58,6 +58,7 @@ pub enum Expr {
     IntegerLiteral(i64),
     FloatLiteral(f64),
     StringLiteral(String),
+    CharLiteral(char),
     Do(Vec<ExprId>),
     Bind(PatternId, ExprId),
     FieldAccess(String, ExprId),
@@ -89,6 +90,7 @@ impl fmt::Display for Expr {
             Expr::IntegerLiteral(v) => write!(f, "Integer({})", v),
             Expr::FloatLiteral(v) => write!(f, "Float({})", v),
             Expr::StringLiteral(v) => write!(f, "String({})", v),
+            Expr::CharLiteral(v) => write!(f, "Char({:?})", v),
             Expr::Do(items) => write!(f, "Do({})", format_list(items)),
             Expr::Bind(t, expr) => write!(f, "Bind({}, {})", t, expr),
             Expr::FieldAccess(name, expr) => write!(f, "FieldAccess({}, {})", name, expr),
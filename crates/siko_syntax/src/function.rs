@@ -1,11 +1,21 @@
 use crate::class::Constraint;
+use crate::expr::Case;
 use crate::expr::ExprId;
+use crate::pattern::PatternId;
 use crate::types::TypeSignatureId;
 use siko_location_info::location_id::LocationId;
 
+#[derive(Debug, Clone)]
+pub struct FunctionClause {
+    pub args: Vec<PatternId>,
+    pub case: Case,
+    pub location_id: LocationId,
+}
+
 #[derive(Debug, Clone)]
 pub enum FunctionBody {
     Expr(ExprId),
+    Clauses(ExprId, Vec<FunctionClause>),
     Extern,
 }
 
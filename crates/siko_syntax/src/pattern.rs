@@ -37,6 +37,7 @@ pub enum Pattern {
     IntegerLiteral(i64),
     FloatLiteral(f64),
     StringLiteral(String),
+    CharLiteral(char),
     Typed(PatternId, TypeSignatureId),
     Record(String, Vec<RecordFieldPattern>),
     Or(Vec<PatternId>),
@@ -31,7 +31,7 @@ use crate::types::TypeSignatureId;
 use siko_location_info::item::ItemInfo;
 use siko_util::ItemContainer;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Program {
     pub modules: ItemContainer<ModuleId, Module>,
     pub functions: ItemContainer<FunctionId, Function>,
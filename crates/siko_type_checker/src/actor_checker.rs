@@ -0,0 +1,136 @@
+use crate::error::TypecheckError;
+use siko_constants::ACTOR_MODULE_NAME;
+use siko_ir::actor::Actor;
+use siko_ir::data::TypeDefId;
+use siko_ir::expr::Expr;
+use siko_ir::expr::ExprId;
+use siko_ir::function::FunctionId;
+use siko_ir::function::FunctionInfo;
+use siko_ir::pattern::Pattern;
+use siko_ir::pattern::PatternId;
+use siko_ir::program::Program;
+use siko_ir::types::Type;
+use siko_ir::walker::Visitor;
+use std::collections::BTreeMap;
+
+pub struct ActorChecker<'a> {
+    program: &'a Program,
+    errors: &'a mut Vec<TypecheckError>,
+    spawn: Option<FunctionId>,
+    send: Option<FunctionId>,
+    actors: BTreeMap<TypeDefId, &'a Actor>,
+}
+
+impl<'a> ActorChecker<'a> {
+    pub fn new(program: &'a Program, errors: &'a mut Vec<TypecheckError>) -> ActorChecker<'a> {
+        let mut spawn = None;
+        let mut send = None;
+        for (id, function) in &program.functions.items {
+            if let FunctionInfo::NamedFunction(info) = &function.info {
+                if info.module != ACTOR_MODULE_NAME {
+                    continue;
+                }
+                match info.name.as_ref() {
+                    "spawn" => spawn = Some(*id),
+                    "send" => send = Some(*id),
+                    _ => {}
+                }
+            }
+        }
+        let actors = program
+            .actors
+            .items
+            .values()
+            .map(|actor| (actor.type_id, actor))
+            .collect();
+        ActorChecker {
+            program: program,
+            errors: errors,
+            spawn: spawn,
+            send: send,
+            actors: actors,
+        }
+    }
+
+    fn get_expr_type(&self, expr_id: &ExprId) -> &'a Type {
+        self.program
+            .expr_types
+            .get(expr_id)
+            .expect("Expr type not found")
+    }
+
+    fn get_actor(&self, ty: &Type) -> Option<&'a Actor> {
+        match ty {
+            Type::Named(_, id, _) => self.actors.get(id).cloned(),
+            _ => None,
+        }
+    }
+
+    fn check_spawn(&mut self, args: &[ExprId]) {
+        let actor_ty = self.get_expr_type(&args[0]);
+        match actor_ty {
+            Type::Var(..) | Type::FixedTypeArg(..) => {}
+            _ => {
+                if self.get_actor(actor_ty).is_none() {
+                    let location_id = self.program.exprs.get(&args[0]).location_id;
+                    let err = TypecheckError::NotAnActor(
+                        actor_ty.get_resolved_type_string(self.program),
+                        location_id,
+                    );
+                    self.errors.push(err);
+                }
+            }
+        }
+    }
+
+    fn check_send(&mut self, args: &[ExprId]) {
+        let pid_ty = self.get_expr_type(&args[0]);
+        let actor = match pid_ty {
+            Type::Named(_, _, type_args) => self.get_actor(&type_args[0]),
+            _ => None,
+        };
+        let actor = match actor {
+            Some(actor) => actor,
+            None => {
+                return;
+            }
+        };
+        let message_ty = self.get_expr_type(&args[1]);
+        let handled = match message_ty {
+            Type::Var(..) | Type::FixedTypeArg(..) => true,
+            Type::Named(_, id, _) => actor
+                .handlers
+                .iter()
+                .any(|handler| handler.protocol_id == *id),
+            _ => false,
+        };
+        if !handled {
+            let location_id = self.program.exprs.get(&args[1]).location_id;
+            let err = TypecheckError::UnhandledMessage(
+                actor.name.clone(),
+                message_ty.get_resolved_type_string(self.program),
+                location_id,
+            );
+            self.errors.push(err);
+        }
+    }
+}
+
+impl<'a> Visitor for ActorChecker<'a> {
+    fn get_program(&self) -> &Program {
+        self.program
+    }
+
+    fn visit_expr(&mut self, _: ExprId, expr: &Expr) {
+        if let Expr::StaticFunctionCall(id, args) = expr {
+            if Some(*id) == self.spawn && args.len() == 1 {
+                self.check_spawn(args);
+            }
+            if Some(*id) == self.send && args.len() == 2 {
+                self.check_send(args);
+            }
+        }
+    }
+
+    fn visit_pattern(&mut self, _: PatternId, _: &Pattern) {}
+}
@@ -17,6 +17,11 @@ pub enum TypecheckError {
     CyclicClassDependencies(LocationId, String),
     MissingInstance(String, LocationId),
     ClassNotAutoDerivable(String, LocationId),
+    InvalidProtocolHandler(String, String, String, LocationId),
+    NotAnActor(String, LocationId),
+    UnhandledMessage(String, String, LocationId),
+    NonExhaustivePatterns(LocationId, Vec<String>),
+    UnreachableCaseArm(LocationId),
 }
 
 #[derive(Debug)]
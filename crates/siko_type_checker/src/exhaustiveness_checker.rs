@@ -0,0 +1,320 @@
+use crate::error::TypecheckError;
+use siko_ir::data::TypeDefId;
+use siko_ir::expr::Case;
+use siko_ir::expr::Expr;
+use siko_ir::expr::ExprId;
+use siko_ir::pattern::Pattern;
+use siko_ir::pattern::PatternId;
+use siko_ir::program::Program;
+use siko_ir::walker::Visitor;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Constructor {
+    Tuple(usize),
+    Record(TypeDefId),
+    Variant(TypeDefId, usize),
+    Integer(i64),
+    Float(u64),
+    String(String),
+    Char(char),
+}
+
+#[derive(Debug, Clone)]
+enum SimplePattern {
+    Wildcard,
+    Constructor(Constructor, Vec<SimplePattern>),
+}
+
+type Row = Vec<SimplePattern>;
+
+pub struct ExhaustivenessChecker<'a> {
+    program: &'a Program,
+    errors: &'a mut Vec<TypecheckError>,
+}
+
+impl<'a> ExhaustivenessChecker<'a> {
+    pub fn new(
+        program: &'a Program,
+        errors: &'a mut Vec<TypecheckError>,
+    ) -> ExhaustivenessChecker<'a> {
+        ExhaustivenessChecker {
+            program: program,
+            errors: errors,
+        }
+    }
+
+    fn simplify(&self, pattern_id: &PatternId) -> SimplePattern {
+        let pattern = &self.program.patterns.get(pattern_id).item;
+        match pattern {
+            Pattern::Binding(_) | Pattern::Wildcard => SimplePattern::Wildcard,
+            Pattern::Tuple(items) => SimplePattern::Constructor(
+                Constructor::Tuple(items.len()),
+                items.iter().map(|item| self.simplify(item)).collect(),
+            ),
+            Pattern::Record(id, items) => SimplePattern::Constructor(
+                Constructor::Record(*id),
+                items.iter().map(|item| self.simplify(item)).collect(),
+            ),
+            Pattern::Variant(id, index, items) => SimplePattern::Constructor(
+                Constructor::Variant(*id, *index),
+                items.iter().map(|item| self.simplify(item)).collect(),
+            ),
+            Pattern::Guarded(id, _) => self.simplify(id),
+            Pattern::IntegerLiteral(v) => {
+                SimplePattern::Constructor(Constructor::Integer(*v), vec![])
+            }
+            Pattern::FloatLiteral(v) => {
+                SimplePattern::Constructor(Constructor::Float(v.to_bits()), vec![])
+            }
+            Pattern::StringLiteral(v) => {
+                SimplePattern::Constructor(Constructor::String(v.clone()), vec![])
+            }
+            Pattern::CharLiteral(v) => SimplePattern::Constructor(Constructor::Char(*v), vec![]),
+            Pattern::Typed(id, _) => self.simplify(id),
+        }
+    }
+
+    fn is_guarded(&self, pattern_id: &PatternId) -> bool {
+        match &self.program.patterns.get(pattern_id).item {
+            Pattern::Guarded(..) => true,
+            Pattern::Typed(id, _) => self.is_guarded(id),
+            _ => false,
+        }
+    }
+
+    fn get_arity(&self, constructor: &Constructor) -> usize {
+        match constructor {
+            Constructor::Tuple(arity) => *arity,
+            Constructor::Record(id) => self.program.typedefs.get(id).get_record().fields.len(),
+            Constructor::Variant(id, index) => {
+                let adt = self.program.typedefs.get(id).get_adt();
+                adt.variants[*index].items.len()
+            }
+            _ => 0,
+        }
+    }
+
+    // Returns the full list of constructors of the type, if all of them appear in the given list
+    fn get_complete_signature(&self, constructors: &[Constructor]) -> Option<Vec<Constructor>> {
+        match constructors.first()? {
+            Constructor::Tuple(arity) => Some(vec![Constructor::Tuple(*arity)]),
+            Constructor::Record(id) => Some(vec![Constructor::Record(*id)]),
+            Constructor::Variant(id, _) => {
+                let adt = self.program.typedefs.get(id).get_adt();
+                let all: Vec<_> = (0..adt.variants.len())
+                    .map(|index| Constructor::Variant(*id, index))
+                    .collect();
+                if all.iter().all(|c| constructors.contains(c)) {
+                    Some(all)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn get_head_constructors(&self, matrix: &[Row]) -> Vec<Constructor> {
+        let mut constructors = Vec::new();
+        for row in matrix {
+            if let SimplePattern::Constructor(c, _) = &row[0] {
+                if !constructors.contains(c) {
+                    constructors.push(c.clone());
+                }
+            }
+        }
+        constructors
+    }
+
+    fn specialize(&self, matrix: &[Row], constructor: &Constructor) -> Vec<Row> {
+        let arity = self.get_arity(constructor);
+        let mut result = Vec::new();
+        for row in matrix {
+            match &row[0] {
+                SimplePattern::Wildcard => {
+                    let mut new_row = vec![SimplePattern::Wildcard; arity];
+                    new_row.extend(row[1..].iter().cloned());
+                    result.push(new_row);
+                }
+                SimplePattern::Constructor(c, items) => {
+                    if c == constructor {
+                        let mut new_row = items.clone();
+                        new_row.extend(row[1..].iter().cloned());
+                        result.push(new_row);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn default_matrix(&self, matrix: &[Row]) -> Vec<Row> {
+        matrix
+            .iter()
+            .filter(|row| match row[0] {
+                SimplePattern::Wildcard => true,
+                _ => false,
+            })
+            .map(|row| row[1..].to_vec())
+            .collect()
+    }
+
+    fn is_useful(&self, matrix: &[Row], row: &Row) -> bool {
+        if row.is_empty() {
+            return matrix.is_empty();
+        }
+        match &row[0] {
+            SimplePattern::Constructor(c, items) => {
+                let mut new_row = items.clone();
+                new_row.extend(row[1..].iter().cloned());
+                self.is_useful(&self.specialize(matrix, c), &new_row)
+            }
+            SimplePattern::Wildcard => {
+                let constructors = self.get_head_constructors(matrix);
+                match self.get_complete_signature(&constructors) {
+                    Some(signature) => signature.iter().any(|c| {
+                        let mut new_row = vec![SimplePattern::Wildcard; self.get_arity(c)];
+                        new_row.extend(row[1..].iter().cloned());
+                        self.is_useful(&self.specialize(matrix, c), &new_row)
+                    }),
+                    None => self.is_useful(&self.default_matrix(matrix), &row[1..].to_vec()),
+                }
+            }
+        }
+    }
+
+    // Collects value vectors of the given width that are not matched by any row
+    fn get_missing(&self, matrix: &[Row], width: usize) -> Vec<Row> {
+        if width == 0 {
+            if matrix.is_empty() {
+                return vec![vec![]];
+            } else {
+                return vec![];
+            }
+        }
+        let constructors = self.get_head_constructors(matrix);
+        let mut result = Vec::new();
+        match self.get_complete_signature(&constructors) {
+            Some(signature) => {
+                for c in signature {
+                    let arity = self.get_arity(&c);
+                    let specialized = self.specialize(matrix, &c);
+                    for mut missing in self.get_missing(&specialized, arity + width - 1) {
+                        let rest = missing.split_off(arity);
+                        let mut row = vec![SimplePattern::Constructor(c.clone(), missing)];
+                        row.extend(rest);
+                        result.push(row);
+                    }
+                }
+            }
+            None => {
+                let missing_rest = self.get_missing(&self.default_matrix(matrix), width - 1);
+                if missing_rest.is_empty() {
+                    return result;
+                }
+                let heads = match constructors.first() {
+                    Some(Constructor::Variant(id, _)) => {
+                        let adt = self.program.typedefs.get(id).get_adt();
+                        (0..adt.variants.len())
+                            .map(|index| Constructor::Variant(*id, index))
+                            .filter(|c| !constructors.contains(c))
+                            .map(|c| {
+                                let items = vec![SimplePattern::Wildcard; self.get_arity(&c)];
+                                SimplePattern::Constructor(c, items)
+                            })
+                            .collect()
+                    }
+                    _ => vec![SimplePattern::Wildcard],
+                };
+                for head in heads {
+                    for missing in &missing_rest {
+                        let mut row = vec![head.clone()];
+                        row.extend(missing.iter().cloned());
+                        result.push(row);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn format_pattern(&self, pattern: &SimplePattern, nested: bool) -> String {
+        match pattern {
+            SimplePattern::Wildcard => format!("_"),
+            SimplePattern::Constructor(c, items) => {
+                let name = match c {
+                    Constructor::Tuple(_) => {
+                        let items: Vec<_> = items
+                            .iter()
+                            .map(|item| self.format_pattern(item, false))
+                            .collect();
+                        return format!("({})", items.join(", "));
+                    }
+                    Constructor::Record(id) => {
+                        self.program.typedefs.get(id).get_record().name.clone()
+                    }
+                    Constructor::Variant(id, index) => {
+                        let adt = self.program.typedefs.get(id).get_adt();
+                        adt.variants[*index].name.clone()
+                    }
+                    Constructor::Integer(v) => format!("{}", v),
+                    Constructor::Float(v) => format!("{}", f64::from_bits(*v)),
+                    Constructor::String(v) => format!("{:?}", v),
+                    Constructor::Char(v) => format!("{:?}", v),
+                };
+                if items.is_empty() {
+                    return name;
+                }
+                let items: Vec<_> = items
+                    .iter()
+                    .map(|item| self.format_pattern(item, true))
+                    .collect();
+                let result = format!("{} {}", name, items.join(" "));
+                if nested {
+                    format!("({})", result)
+                } else {
+                    result
+                }
+            }
+        }
+    }
+
+    fn check_case(&mut self, expr_id: ExprId, cases: &[Case]) {
+        let mut matrix = Vec::new();
+        for case in cases {
+            let row = vec![self.simplify(&case.pattern_id)];
+            if !self.is_useful(&matrix, &row) {
+                let location_id = self.program.patterns.get(&case.pattern_id).location_id;
+                let err = TypecheckError::UnreachableCaseArm(location_id);
+                self.errors.push(err);
+            }
+            if !self.is_guarded(&case.pattern_id) {
+                matrix.push(row);
+            }
+        }
+        let missing = self.get_missing(&matrix, 1);
+        if !missing.is_empty() {
+            let missing: Vec<_> = missing
+                .iter()
+                .map(|row| self.format_pattern(&row[0], false))
+                .collect();
+            let location_id = self.program.exprs.get(&expr_id).location_id;
+            let err = TypecheckError::NonExhaustivePatterns(location_id, missing);
+            self.errors.push(err);
+        }
+    }
+}
+
+impl<'a> Visitor for ExhaustivenessChecker<'a> {
+    fn get_program(&self) -> &Program {
+        &self.program
+    }
+
+    fn visit_expr(&mut self, expr_id: ExprId, expr: &Expr) {
+        if let Expr::CaseOf(_, cases, _) = expr {
+            self.check_case(expr_id, cases);
+        }
+    }
+
+    fn visit_pattern(&mut self, _: PatternId, _: &Pattern) {}
+}
@@ -252,6 +252,7 @@ impl<'a> Visitor for ExpressionChecker<'a> {
                 self.check_function_call(expr_id, args);
             }
             Expr::StringLiteral(_) => {}
+            Expr::CharLiteral(_) => {}
             Expr::RecordInitialization(_, values) => {
                 let record_type_info = self
                     .type_store
@@ -357,6 +358,7 @@ impl<'a> Visitor for ExpressionChecker<'a> {
                 }
             }
             Pattern::StringLiteral(_) => {}
+            Pattern::CharLiteral(_) => {}
             Pattern::Tuple(items) => {
                 let ty = self.type_store.get_pattern_type(&pattern_id).clone();
                 if let Type::Tuple(item_types) = ty {
@@ -1,6 +1,8 @@
+pub mod actor_checker;
 pub mod common;
 pub mod dependency_processor;
 pub mod error;
+pub mod exhaustiveness_checker;
 pub mod expression_checker;
 pub mod function_dep_processor;
 pub mod instance_resolver;
@@ -162,6 +162,10 @@ impl TypeStore {
         }
     }
 
+    pub fn get_expr_ids(&self) -> Vec<ExprId> {
+        self.expr_types.keys().cloned().collect()
+    }
+
     pub fn get_func_type_for_expr(&self, expr_id: &ExprId) -> &FunctionTypeInfo {
         match self.expr_types.get(expr_id).expect("Expr type not found") {
             ExpressionTypeState::ExprType(_) => unreachable!(),
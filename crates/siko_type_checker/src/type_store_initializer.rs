@@ -197,6 +197,10 @@ impl<'a> Visitor for TypeStoreInitializer<'a> {
                 self.type_store
                     .initialize_expr(expr_id, self.program.get_string_type());
             }
+            Expr::CharLiteral(_) => {
+                self.type_store
+                    .initialize_expr(expr_id, self.program.get_char_type());
+            }
             Expr::RecordInitialization(id, _) => {
                 let record_type_info = self.type_info_provider.get_record_type_info(id);
                 let ty = record_type_info.record_type.clone();
@@ -281,6 +285,10 @@ impl<'a> Visitor for TypeStoreInitializer<'a> {
                 self.type_store
                     .initialize_pattern(pattern_id, self.program.get_string_type());
             }
+            Pattern::CharLiteral(_) => {
+                self.type_store
+                    .initialize_pattern(pattern_id, self.program.get_char_type());
+            }
             Pattern::Typed(_, type_signature) => {
                 let ty = process_type_signature(
                     *type_signature,
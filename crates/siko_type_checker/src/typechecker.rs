@@ -1,3 +1,4 @@
+use crate::actor_checker::ActorChecker;
 use crate::common::AdtTypeInfo;
 use crate::common::ClassMemberTypeInfo;
 use crate::common::DeriveInfo;
@@ -7,6 +8,7 @@ use crate::common::VariantTypeInfo;
 use crate::dependency_processor::DependencyGroup;
 use crate::error::Error;
 use crate::error::TypecheckError;
+use crate::exhaustiveness_checker::ExhaustivenessChecker;
 use crate::expression_checker::ExpressionChecker;
 use crate::function_dep_processor::FunctionDependencyProcessor;
 use crate::instance_resolver::InstanceResolver;
@@ -16,8 +18,11 @@ use crate::type_store_initializer::TypeStoreInitializer;
 use crate::undefined_var_checker::UndefinedVarChecker;
 use crate::util::create_general_function_type;
 use crate::util::process_type_signature;
+use siko_constants::RESULT_MODULE_NAME;
+use siko_constants::RESULT_TYPE_NAME;
 use siko_ir::class::ClassId;
 use siko_ir::data::TypeDef;
+use siko_ir::expr::Expr;
 use siko_ir::expr::ExprId;
 use siko_ir::function::Function;
 use siko_ir::function::FunctionId;
@@ -196,7 +201,11 @@ impl Typechecker {
                     let mut derived_classes = Vec::new();
                     for derived_class in &adt.derived_classes {
                         let class = program.classes.get(&derived_class.class_id);
-                        if !class.auto_derivable {
+                        // only variants without items can be enumerated
+                        let not_enumerable = class.module == "Std.Ops"
+                            && class.name == "Enum"
+                            && adt.variants.iter().any(|v| !v.items.is_empty());
+                        if !class.auto_derivable || not_enumerable {
                             let err = TypecheckError::ClassNotAutoDerivable(
                                 class.name.clone(),
                                 derived_class.location_id,
@@ -248,7 +257,8 @@ impl Typechecker {
                     let mut derived_classes = Vec::new();
                     for derived_class in &record.derived_classes {
                         let class = program.classes.get(&derived_class.class_id);
-                        if !class.auto_derivable {
+                        let not_enumerable = class.module == "Std.Ops" && class.name == "Enum";
+                        if !class.auto_derivable || not_enumerable {
                             let err = TypecheckError::ClassNotAutoDerivable(
                                 class.name.clone(),
                                 derived_class.location_id,
@@ -571,6 +581,132 @@ impl Typechecker {
         }
     }
 
+    // An error returned by main is reported with its Show instance when the program exits
+    fn check_main_result(
+        &self,
+        program: &Program,
+        type_info_provider: &TypeInfoProvider,
+        instance_resolver: &mut InstanceResolver,
+        errors: &mut Vec<TypecheckError>,
+    ) {
+        let result_id = program.get_named_type(RESULT_MODULE_NAME, RESULT_TYPE_NAME);
+        let show_id = *program.class_names.get("Show").expect("Show not found");
+        for (id, function) in &program.functions.items {
+            if let FunctionInfo::NamedFunction(info) = &function.info {
+                if info.module != siko_constants::MAIN_MODULE
+                    || info.name != siko_constants::MAIN_FUNCTION
+                {
+                    continue;
+                }
+                let function_type_info = type_info_provider.function_type_info_store.get(id);
+                if let Type::Named(_, type_id, type_args) = &function_type_info.result {
+                    if *type_id == result_id && type_args[1].is_concrete_type() {
+                        let mut unifiers = Vec::new();
+                        if !instance_resolver.check_instance(
+                            show_id,
+                            &type_args[1],
+                            info.location_id,
+                            &mut unifiers,
+                        ) {
+                            let err =
+                                TypecheckError::MissingInstance(format!("Show"), info.location_id);
+                            errors.push(err);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_actors(
+        &self,
+        program: &mut Program,
+        type_info_provider: &mut TypeInfoProvider,
+        instance_resolver: &mut InstanceResolver,
+        errors: &mut Vec<TypecheckError>,
+    ) {
+        for (_, actor) in &program.actors.items {
+            let state_ty = process_type_signature(
+                actor.type_signature,
+                program,
+                &mut type_info_provider.type_var_generator,
+            );
+            for handler in &actor.handlers {
+                let adt_type_info = type_info_provider.get_adt_type_info(&handler.protocol_id);
+                let message_ty = adt_type_info.variant_types[0].item_types[0].0.clone();
+                let expected_ty = Type::Function(
+                    Box::new(state_ty.clone()),
+                    Box::new(Type::Function(
+                        Box::new(message_ty),
+                        Box::new(state_ty.clone()),
+                    )),
+                );
+                let handler_type_info =
+                    type_info_provider.get_function_type(&handler.handler_func, true);
+                let mut unifier = Unifier::new(type_info_provider.type_var_generator.clone());
+                let mut failed = false;
+                if unifier
+                    .unify(&expected_ty, &handler_type_info.function_type)
+                    .is_ok()
+                {
+                    for constraint in unifier.get_constraints() {
+                        let mut unifiers = Vec::new();
+                        if !instance_resolver.check_instance(
+                            constraint.class_id,
+                            &constraint.ty,
+                            handler.handler_func_location_id,
+                            &mut unifiers,
+                        ) {
+                            failed = true;
+                            break;
+                        }
+                    }
+                } else {
+                    failed = true;
+                }
+                if failed {
+                    let err = TypecheckError::InvalidProtocolHandler(
+                        handler_type_info.displayed_name.clone(),
+                        expected_ty.get_resolved_type_string(program),
+                        handler_type_info
+                            .function_type
+                            .get_resolved_type_string(program),
+                        handler.handler_func_location_id,
+                    );
+                    errors.push(err);
+                }
+            }
+        }
+    }
+
+    fn check_actor_messages(&self, program: &Program, errors: &mut Vec<TypecheckError>) {
+        let mut checker = ActorChecker::new(program, errors);
+        for (_, function) in &program.functions.items {
+            let body = match &function.info {
+                FunctionInfo::NamedFunction(info) => info.body,
+                FunctionInfo::Lambda(info) => Some(info.body),
+                _ => None,
+            };
+            if let Some(body) = body {
+                walk_expr(&body, &mut checker);
+            }
+        }
+    }
+
+    fn check_case_coverage(&self, program: &Program, errors: &mut Vec<TypecheckError>) {
+        for (_, function) in &program.functions.items {
+            let body = match &function.info {
+                FunctionInfo::NamedFunction(info) => info.body,
+                FunctionInfo::Lambda(info) => Some(info.body),
+                _ => None,
+            };
+            if let Some(body) = body {
+                let mut checker = ExhaustivenessChecker::new(program, errors);
+                walk_expr(&body, &mut checker);
+            }
+        }
+    }
+
     fn init_expr_types<'a>(
         &self,
         function_id: &FunctionId,
@@ -655,6 +791,64 @@ impl Typechecker {
                 program,
             );
         }
+
+        self.resolve_call_constraints(
+            group,
+            type_store,
+            type_info_provider,
+            instance_resolver,
+            program,
+        );
+    }
+
+    // The type arguments of a call may only become concrete after its constraints were
+    // checked, so the instances needed by the concrete calls are resolved once the group
+    // is typed.
+    fn resolve_call_constraints(
+        &self,
+        group: &DependencyGroup<FunctionId>,
+        type_store: &TypeStore,
+        type_info_provider: &mut TypeInfoProvider,
+        instance_resolver: &mut InstanceResolver,
+        program: &Program,
+    ) {
+        for expr_id in type_store.get_expr_ids() {
+            let expr = program.exprs.get(&expr_id);
+            let callee_ty = match &expr.item {
+                Expr::StaticFunctionCall(function_id, _) => {
+                    if group.items.contains(function_id) {
+                        continue;
+                    }
+                    type_info_provider
+                        .get_function_type(function_id, true)
+                        .function_type
+                }
+                Expr::ClassFunctionCall(class_member_id, _) => {
+                    type_info_provider.get_class_member_type(class_member_id)
+                }
+                _ => continue,
+            };
+            let func_type_info = type_store.get_func_type_for_expr(&expr_id);
+            let mut unifier = program.get_unifier();
+            if unifier
+                .unify(&callee_ty, &func_type_info.function_type)
+                .is_err()
+            {
+                continue;
+            }
+            for constraint in unifier.get_constraints() {
+                let ty = unifier.apply(&constraint.ty);
+                if ty.is_concrete_type() {
+                    let mut unifiers = Vec::new();
+                    instance_resolver.check_instance(
+                        constraint.class_id,
+                        &ty,
+                        expr.location_id,
+                        &mut unifiers,
+                    );
+                }
+            }
+        }
     }
 
     fn process_class_members(
@@ -686,7 +880,7 @@ impl Typechecker {
         }
     }
 
-    pub fn check(&self, program: &mut Program) -> Result<(), Error> {
+    pub fn check(&self, program: &mut Program, main_required: bool) -> Result<(), Error> {
         let mut errors = Vec::new();
         let mut type_var_generator = program.type_var_generator.clone();
         let mut type_info_provider = TypeInfoProvider::new(type_var_generator.clone());
@@ -752,7 +946,9 @@ impl Typechecker {
 
         let ordered_dep_groups = function_dep_processor.process_functions();
 
-        self.check_main(program, &mut errors);
+        if main_required {
+            self.check_main(program, &mut errors);
+        }
 
         if !errors.is_empty() {
             return Err(Error::typecheck_err(errors));
@@ -776,6 +972,42 @@ impl Typechecker {
             return Err(Error::typecheck_err(errors));
         }
 
+        self.check_case_coverage(program, &mut errors);
+
+        if !errors.is_empty() {
+            return Err(Error::typecheck_err(errors));
+        }
+
+        self.check_actors(
+            program,
+            &mut type_info_provider,
+            &mut instance_resolver,
+            &mut errors,
+        );
+
+        if !errors.is_empty() {
+            return Err(Error::typecheck_err(errors));
+        }
+
+        self.check_actor_messages(program, &mut errors);
+
+        if !errors.is_empty() {
+            return Err(Error::typecheck_err(errors));
+        }
+
+        if main_required {
+            self.check_main_result(
+                program,
+                &type_info_provider,
+                &mut instance_resolver,
+                &mut errors,
+            );
+        }
+
+        if !errors.is_empty() {
+            return Err(Error::typecheck_err(errors));
+        }
+
         //type_info_provider.function_type_info_store.dump(program);
 
         type_info_provider
@@ -47,7 +47,7 @@ pub fn format_list<T: fmt::Display>(items: &[T]) -> String {
     format!("{}", ss.join(", "))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ItemContainer<Key, Item> {
     pub items: BTreeMap<Key, Item>,
     id: Counter,
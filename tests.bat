@@ -2,7 +2,12 @@
 call ./build.bat
 SETLOCAL EnableDelayedExpansion
 @echo off
-for /R "tests" %%A in ("*.sk") do echo "processing %%~fA" && .\siko %%~fA
+for /R "tests" %%A in ("*.sk") do (
+    echo "processing %%~fA"
+    set FLAGS=
+    if exist "%%~dpAflags.txt" set /p FLAGS=<"%%~dpAflags.txt"
+    if exist "%%~dpAinput.txt" (.\siko !FLAGS! %%~fA < "%%~dpAinput.txt") else (.\siko !FLAGS! %%~fA)
+)
 
 echo on
 
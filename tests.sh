@@ -3,8 +3,28 @@
 ./build.sh
 
 TESTS=$(find tests -mindepth 1 -maxdepth 1)
+FAILED=0
 
 for TEST in $TESTS; do
     echo "Running $TEST"
-    ./siko $TEST
+    FLAGS=""
+    if [ -f $TEST/flags.txt ]; then
+        FLAGS=$(cat $TEST/flags.txt)
+    fi
+    EXPECTED=0
+    if [ -f $TEST/exit_code.txt ]; then
+        EXPECTED=$(cat $TEST/exit_code.txt)
+    fi
+    if [ -f $TEST/input.txt ]; then
+        ./siko $FLAGS $TEST < $TEST/input.txt
+    else
+        ./siko $FLAGS $TEST
+    fi
+    CODE=$?
+    if [ $CODE -ne $EXPECTED ]; then
+        echo "$TEST exited with $CODE, expected $EXPECTED"
+        FAILED=1
+    fi
 done
+
+exit $FAILED